@@ -0,0 +1,129 @@
+//! Free ONT ID and service-port index allocation.
+//!
+//! `ont_add` and `service_port_add` let the OLT pick the next ID, which is hard
+//! to predict from an OSS. These helpers compute free IDs from what the OLT
+//! reports so callers can reserve them up front and pass them explicitly to
+//! [`Connection::ont_add_with_id`] and [`Connection::service_port_add_with_index`].
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::RangeInclusive;
+
+use crate::error::Result;
+use crate::models::{Fsp, ServicePort};
+use crate::ont_info_summary::{OntInfoSummary, OntInfoSummaryPort};
+use crate::ssh::Connection;
+
+/// Highest ONT ID accepted on a GPON port (IDs range from 0 to 127).
+pub const GPON_MAX_ONT_ID: u32 = 127;
+
+/// Returns the ONT IDs not used on `port`, in ascending order.
+#[must_use]
+pub fn free_ont_ids(port: &OntInfoSummaryPort) -> Vec<u32> {
+    let used: BTreeSet<u32> = port.onts.iter().map(|o| o.id).collect();
+    (0..=GPON_MAX_ONT_ID)
+        .filter(|id| !used.contains(id))
+        .collect()
+}
+
+/// Returns up to `count` service-port indexes within `range` not used by `ports`.
+#[must_use]
+pub fn free_service_port_indexes(
+    ports: &[ServicePort],
+    range: RangeInclusive<u32>,
+    count: usize,
+) -> Vec<u32> {
+    let used: BTreeSet<u32> = ports.iter().map(|p| p.index).collect();
+    range
+        .filter(|index| !used.contains(index))
+        .take(count)
+        .collect()
+}
+
+/// Hands out free ONT IDs per port, remembering what was already reserved.
+#[derive(Debug, Clone, Default)]
+pub struct OntIdAllocator {
+    used: HashMap<Fsp, BTreeSet<u32>>,
+}
+
+impl OntIdAllocator {
+    #[must_use]
+    pub fn from_summary(summary: &OntInfoSummary) -> Self {
+        let mut allocator = Self::default();
+        for port in &summary.ports {
+            allocator
+                .used
+                .entry(port.fsp)
+                .or_default()
+                .extend(port.onts.iter().map(|o| o.id));
+        }
+        allocator
+    }
+
+    /// Reserves the lowest free ONT ID on `fsp`, or `None` if the port is full.
+    pub fn reserve(&mut self, fsp: Fsp) -> Option<u32> {
+        let used = self.used.entry(fsp).or_default();
+        let id = (0..=GPON_MAX_ONT_ID).find(|id| !used.contains(id))?;
+        used.insert(id);
+        Some(id)
+    }
+
+    /// Makes a previously reserved ID available again.
+    pub fn release(&mut self, fsp: Fsp, id: u32) {
+        if let Some(used) = self.used.get_mut(&fsp) {
+            used.remove(&id);
+        }
+    }
+}
+
+/// Hands out free service-port indexes, remembering what was already reserved.
+#[derive(Debug, Clone)]
+pub struct ServicePortIndexAllocator {
+    range: RangeInclusive<u32>,
+    used: BTreeSet<u32>,
+}
+
+impl ServicePortIndexAllocator {
+    #[must_use]
+    pub fn new(ports: &[ServicePort], range: RangeInclusive<u32>) -> Self {
+        Self {
+            range,
+            used: ports.iter().map(|p| p.index).collect(),
+        }
+    }
+
+    /// Reserves the lowest free index in range, or `None` if the range is exhausted.
+    pub fn reserve(&mut self) -> Option<u32> {
+        let index = self.range.clone().find(|i| !self.used.contains(i))?;
+        self.used.insert(index);
+        Some(index)
+    }
+
+    pub fn release(&mut self, index: u32) {
+        self.used.remove(&index);
+    }
+}
+
+/// Reads the summary of `fsp` and returns its free ONT IDs.
+pub fn query_free_ont_ids(conn: &mut Connection, fsp: Fsp) -> Result<Vec<u32>> {
+    conn.ensure_config()?;
+    let summary = conn.display_ont_info_summary(fsp.frame, fsp.slot, fsp.port)?;
+
+    Ok(summary
+        .ports
+        .iter()
+        .find(|p| p.fsp == fsp)
+        .map_or_else(|| (0..=GPON_MAX_ONT_ID).collect(), free_ont_ids))
+}
+
+/// Reads the whole service-port table and returns up to `count` free indexes
+/// within `range`.
+pub fn query_free_service_port_indexes(
+    conn: &mut Connection,
+    range: RangeInclusive<u32>,
+    count: usize,
+) -> Result<Vec<u32>> {
+    conn.ensure_config()?;
+    let table = conn.display_service_port_all()?;
+
+    Ok(free_service_port_indexes(&table.ports, range, count))
+}
@@ -0,0 +1,548 @@
+//! Bulk ONT provisioning from CSV or JSON manifests.
+//!
+//! Each manifest row describes one ONT as a [`DesiredOnt`]. Rows are validated
+//! before anything is sent to the OLT, then provisioned through the
+//! [`Provisioner`] calls `ont_add`, `ont_port_native_vlan` and
+//! `service_port_add_unchecked` on pooled connections.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::{Error, Result};
+use crate::models::{Fsp, NativeVlan, OntPortType};
+use crate::parser::normalize_serial_number;
+use crate::provisioner::Provisioner;
+use crate::r2d2::ConnectionManager;
+use crate::reconcile::{DesiredOnt, DesiredServicePort};
+use crate::vlan::{check_service_port_vlans, Vlan};
+
+/// ONT port used for the native VLAN when a CSV manifest does not name one.
+pub const DEFAULT_NATIVE_VLAN_PORT: &str = "eth 1";
+
+const MAX_DESCRIPTION_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct ManifestRow {
+    /// 1-based line number in a CSV manifest (the header is line 1), or the
+    /// 1-based position in a JSON manifest
+    pub row: usize,
+    pub ont: DesiredOnt,
+}
+
+/// Rows parsed from a manifest and the rows that could not be parsed
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub rows: Vec<ManifestRow>,
+    pub issues: Vec<RowIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct RowIssue {
+    pub row: usize,
+    pub sn: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RowStatus {
+    /// ONT, native VLANs and service ports were all configured
+    Provisioned,
+    /// A command failed; the ONT may be partially configured
+    Failed,
+    /// The row did not pass validation and was not sent to the OLT
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct RowResult {
+    pub row: usize,
+    pub sn: String,
+    pub fsp: Fsp,
+    pub status: RowStatus,
+    /// ONT ID assigned by the OLT, if the ONT was added
+    pub ont_id: Option<u32>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct BulkReport {
+    pub rows: Vec<RowResult>,
+}
+
+impl BulkReport {
+    #[must_use]
+    pub fn count(&self, status: RowStatus) -> usize {
+        self.rows.iter().filter(|r| r.status == status).count()
+    }
+
+    /// Renders the report as CSV with a header row.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("row,sn,fsp,status,ont_id,message\n");
+        for row in &self.rows {
+            let status = match row.status {
+                RowStatus::Provisioned => "provisioned",
+                RowStatus::Failed => "failed",
+                RowStatus::Invalid => "invalid",
+            };
+            let ont_id = row.ont_id.map(|id| id.to_string()).unwrap_or_default();
+            let message = row.message.as_deref().unwrap_or_default();
+            out.push_str(
+                &[
+                    row.row.to_string(),
+                    csv_escape(&row.sn),
+                    row.fsp.to_string(),
+                    status.to_string(),
+                    ont_id,
+                    csv_escape(message),
+                ]
+                .join(","),
+            );
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Parses a CSV manifest with a header row.
+///
+/// Required columns: `sn`, `fsp`, `description`, `line_profile_id`,
+/// `service_profile_id`. Optional columns: `native_vlan`, `native_vlan_priority`,
+/// `ont_port` (defaults to [`DEFAULT_NATIVE_VLAN_PORT`]) and the service-port
+/// columns `vlan`, `gemport`, `user_vlan`, `inbound_traffic_table`,
+/// `outbound_traffic_table`, which are required whenever `vlan` is set.
+///
+/// Only a missing header or column fails the whole manifest; malformed rows are
+/// returned as [`Manifest::issues`] next to the rows that parsed.
+pub fn parse_csv_manifest(input: &str) -> Result<Manifest> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim_end_matches('\r')))
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let (_, header) = lines
+        .next()
+        .ok_or_else(|| Error::ParseError("Manifest is empty".to_string()))?;
+    let columns: HashMap<String, usize> = split_csv_line(header)
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| (name.trim().to_ascii_lowercase(), i))
+        .collect();
+
+    for required in [
+        "sn",
+        "fsp",
+        "description",
+        "line_profile_id",
+        "service_profile_id",
+    ] {
+        if !columns.contains_key(required) {
+            return Err(Error::ParseError(format!(
+                "Manifest is missing column '{required}'"
+            )));
+        }
+    }
+
+    let mut manifest = Manifest::default();
+    for (row, line) in lines {
+        let fields = split_csv_line(line);
+        let get = |name: &str| -> Option<&str> {
+            columns
+                .get(name)
+                .and_then(|&idx| fields.get(idx))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+        };
+
+        match parse_csv_row(get) {
+            Ok(ont) => manifest.rows.push(ManifestRow { row, ont }),
+            Err(message) => manifest.issues.push(RowIssue {
+                row,
+                sn: get("sn").unwrap_or_default().to_string(),
+                message,
+            }),
+        }
+    }
+
+    Ok(manifest)
+}
+
+fn parse_csv_row<'a>(
+    get: impl Fn(&str) -> Option<&'a str>,
+) -> std::result::Result<DesiredOnt, String> {
+    let required = |name: &str| get(name).ok_or_else(|| format!("'{name}' is empty"));
+    let number = |name: &str| -> std::result::Result<Option<u32>, String> {
+        get(name)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| format!("'{name}' is not a number: {v}"))
+            })
+            .transpose()
+    };
+    let required_number = |name: &str| number(name)?.ok_or_else(|| format!("'{name}' is empty"));
+
+    let fsp_str = required("fsp")?;
+    let fsp = Fsp::parse(fsp_str).ok_or_else(|| format!("invalid F/S/P {fsp_str}"))?;
+
+    let native_vlans = match number("native_vlan")? {
+        Some(vlan) => {
+            let ont_port = get("ont_port").unwrap_or(DEFAULT_NATIVE_VLAN_PORT);
+            let (port_type, ont_port) =
+                parse_ont_port(ont_port).ok_or_else(|| format!("invalid ONT port {ont_port}"))?;
+            vec![NativeVlan {
+                port_type,
+                ont_port,
+                vlan,
+                priority: number("native_vlan_priority")?.unwrap_or(0),
+            }]
+        }
+        None => Vec::new(),
+    };
+
+    let service_ports = match number("vlan")? {
+        Some(vlan) => vec![DesiredServicePort {
+            vlan,
+            gemport: required_number("gemport")?,
+            user_vlan: required_number("user_vlan")?,
+            inbound_traffic_table: required_number("inbound_traffic_table")?,
+            outbound_traffic_table: required_number("outbound_traffic_table")?,
+        }],
+        None => Vec::new(),
+    };
+
+    Ok(DesiredOnt {
+        sn: required("sn")?.to_string(),
+        fsp,
+        description: required("description")?.to_string(),
+        line_profile_id: required_number("line_profile_id")?,
+        service_profile_id: required_number("service_profile_id")?,
+        native_vlans,
+        service_ports,
+    })
+}
+
+/// Parses a JSON manifest: an array of [`DesiredOnt`] objects.
+pub fn parse_json_manifest(input: &str) -> Result<Manifest> {
+    let onts: Vec<DesiredOnt> =
+        serde_json::from_str(input).map_err(|e| Error::ParseError(e.to_string()))?;
+
+    Ok(Manifest {
+        rows: onts
+            .into_iter()
+            .enumerate()
+            .map(|(i, ont)| ManifestRow { row: i + 1, ont })
+            .collect(),
+        issues: Vec::new(),
+    })
+}
+
+/// Checks every row without touching the OLT and returns all problems found.
+#[must_use]
+pub fn validate_manifest(rows: &[ManifestRow]) -> Vec<RowIssue> {
+    let mut issues = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for row in rows {
+        let ont = &row.ont;
+        let mut issue = |message: String| {
+            issues.push(RowIssue {
+                row: row.row,
+                sn: ont.sn.clone(),
+                message,
+            });
+        };
+
+        let sn = normalize_serial_number(&ont.sn);
+        if sn.len() != 16 || !sn.chars().all(|c| c.is_ascii_hexdigit()) {
+            issue(format!("Invalid serial number {}", ont.sn));
+        } else if let Some(first) = seen.insert(sn, row.row) {
+            issue(format!(
+                "Duplicate serial number, first seen on row {first}"
+            ));
+        }
+
+        if ont.description.is_empty() {
+            issue("Description is empty".to_string());
+        } else if ont.description.chars().any(char::is_whitespace) {
+            issue("Description must not contain whitespace".to_string());
+        } else if ont.description.len() > MAX_DESCRIPTION_LEN {
+            issue(format!(
+                "Description is longer than {MAX_DESCRIPTION_LEN} characters"
+            ));
+        }
+
+        for native_vlan in &ont.native_vlans {
+            if !is_valid_vlan(native_vlan.vlan) {
+                issue(format!("Invalid native VLAN {}", native_vlan.vlan));
+            }
+        }
+
+        for service_port in &ont.service_ports {
+            if !is_valid_vlan(service_port.vlan) {
+                issue(format!("Invalid service VLAN {}", service_port.vlan));
+            }
+            if !is_valid_vlan(service_port.user_vlan) {
+                issue(format!("Invalid user VLAN {}", service_port.user_vlan));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Provisions a single ONT and its native VLANs and service ports.
+///
+/// A row that fails validation is reported as [`RowStatus::Invalid`] without
+/// being sent. The service-port VLANs are checked next, so a missing VLAN fails
+/// the row before the ONT is added.
+pub fn provision(conn: &mut impl Provisioner, row: &ManifestRow) -> RowResult {
+    let issues = validate_manifest(std::slice::from_ref(row));
+    if !issues.is_empty() {
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        return invalid_row(row, &messages);
+    }
+
+    let vlans = service_port_vlans(&row.ont);
+    let checked = conn
+        .ensure_config()
+        .and_then(|()| conn.check_service_port_vlans(&vlans));
+
+    match checked {
+        Ok(()) => provision_checked(conn, row),
+        Err(err) => failed_row(row, err.to_string()),
+    }
+}
+
+fn provision_checked(conn: &mut impl Provisioner, row: &ManifestRow) -> RowResult {
+    let ont = &row.ont;
+    let mut result = RowResult {
+        row: row.row,
+        sn: ont.sn.clone(),
+        fsp: ont.fsp,
+        status: RowStatus::Failed,
+        ont_id: None,
+        message: None,
+    };
+
+    match provision_ont(conn, ont, &mut result.ont_id) {
+        Ok(()) => result.status = RowStatus::Provisioned,
+        Err(err) => result.message = Some(err.to_string()),
+    }
+
+    result
+}
+
+fn invalid_row(row: &ManifestRow, messages: &[&str]) -> RowResult {
+    RowResult {
+        row: row.row,
+        sn: row.ont.sn.clone(),
+        fsp: row.ont.fsp,
+        status: RowStatus::Invalid,
+        ont_id: None,
+        message: Some(messages.join("; ")),
+    }
+}
+
+fn failed_row(row: &ManifestRow, message: String) -> RowResult {
+    RowResult {
+        row: row.row,
+        sn: row.ont.sn.clone(),
+        fsp: row.ont.fsp,
+        status: RowStatus::Failed,
+        ont_id: None,
+        message: Some(message),
+    }
+}
+
+fn service_port_vlans(ont: &DesiredOnt) -> Vec<u32> {
+    ont.service_ports.iter().map(|sp| sp.vlan).collect()
+}
+
+/// Reads the OLT's VLANs once for the whole manifest.
+fn read_vlans(pool: &::r2d2::Pool<ConnectionManager>) -> Result<Vec<Vlan>> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| Error::ConnectionFailed(e.to_string()))?;
+    conn.ensure_config()?;
+    conn.display_vlan_all()
+}
+
+fn provision_ont(
+    conn: &mut impl Provisioner,
+    ont: &DesiredOnt,
+    ont_id: &mut Option<u32>,
+) -> Result<()> {
+    conn.ensure_interface_gpon(ont.fsp.frame, ont.fsp.slot)?;
+    let id = conn.ont_add(
+        ont.fsp.port,
+        &ont.sn,
+        &ont.description,
+        ont.line_profile_id,
+        ont.service_profile_id,
+    )?;
+    *ont_id = Some(id);
+
+    for native_vlan in &ont.native_vlans {
+        conn.ont_port_native_vlan(
+            ont.fsp.port,
+            id,
+            native_vlan.port_type,
+            native_vlan.ont_port,
+            native_vlan.vlan,
+            native_vlan.priority,
+        )?;
+    }
+
+    conn.ensure_config()?;
+    for service_port in &ont.service_ports {
+        conn.service_port_add_unchecked(&service_port.to_config(ont.fsp, id)?)?;
+    }
+
+    Ok(())
+}
+
+/// Validates and provisions every row using up to `concurrency` pooled connections.
+///
+/// Rows that could not be parsed or fail validation are reported as
+/// [`RowStatus::Invalid`] and are not sent to the OLT. The service-port VLANs of
+/// all rows are checked against a single `display vlan all`; rows using a
+/// missing VLAN fail without being sent. Results are returned in manifest order.
+#[must_use]
+pub fn run(
+    pool: &::r2d2::Pool<ConnectionManager>,
+    manifest: &Manifest,
+    concurrency: usize,
+) -> BulkReport {
+    let rows = &manifest.rows;
+    let issues = validate_manifest(rows);
+    let mut results: Vec<Option<RowResult>> = vec![None; rows.len()];
+    let mut pending = Vec::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        let messages: Vec<&str> = issues
+            .iter()
+            .filter(|issue| issue.row == row.row)
+            .map(|issue| issue.message.as_str())
+            .collect();
+
+        if messages.is_empty() {
+            pending.push(i);
+        } else {
+            results[i] = Some(invalid_row(row, &messages));
+        }
+    }
+
+    if pending
+        .iter()
+        .any(|&i| !rows[i].ont.service_ports.is_empty())
+    {
+        let vlans = read_vlans(pool);
+        pending.retain(|&i| {
+            let checked = match &vlans {
+                Ok(vlans) => check_service_port_vlans(vlans, &service_port_vlans(&rows[i].ont))
+                    .map_err(|e| e.to_string()),
+                Err(err) => Err(format!("Cannot check VLANs: {err}")),
+            };
+            match checked {
+                Ok(()) => true,
+                Err(message) => {
+                    results[i] = Some(failed_row(&rows[i], message));
+                    false
+                }
+            }
+        });
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(results);
+    let workers = concurrency.clamp(1, pending.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                let Ok(mut conn) = pool.get() else {
+                    return;
+                };
+
+                while let Some(&i) = pending.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let result = provision_checked(&mut *conn, &rows[i]);
+                    if let Ok(mut results) = results.lock() {
+                        results[i] = Some(result);
+                    }
+                }
+            });
+        }
+    });
+
+    let results = results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut report = BulkReport {
+        rows: results
+            .into_iter()
+            .zip(rows)
+            .map(|(result, row)| {
+                result.unwrap_or_else(|| failed_row(row, "No connection available".to_string()))
+            })
+            .collect(),
+    };
+
+    report
+        .rows
+        .extend(manifest.issues.iter().map(|issue| RowResult {
+            row: issue.row,
+            sn: issue.sn.clone(),
+            fsp: Fsp::default(),
+            status: RowStatus::Invalid,
+            ont_id: None,
+            message: Some(issue.message.clone()),
+        }));
+    report.rows.sort_by_key(|r| r.row);
+    report
+}
+
+const fn is_valid_vlan(vlan: u32) -> bool {
+    vlan >= 1 && vlan <= 4094
+}
+
+/// Parses an ONT port such as `eth 1` into its type and number.
+fn parse_ont_port(value: &str) -> Option<(OntPortType, u32)> {
+    let (port_type, number) = value.split_once(char::is_whitespace)?;
+    Some((port_type.parse().ok()?, number.trim().parse().ok()?))
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,152 @@
+//! Saving and retrieving the OLT configuration.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub use crate::parser::{strip_pager, MORE_PROMPT};
+
+/// How long [`crate::Connection::save`] waits for the OLT to finish writing
+pub const SAVE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Progress of a `save` command
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveStatus {
+    InProgress,
+    Succeeded,
+    Failed,
+}
+
+/// Lines the OLT prints once the configuration has been written
+const SAVE_SUCCEEDED_LINES: [&str; 2] = [
+    "Configuration file had been saved successfully",
+    "The data of configuration file had been saved successfully",
+];
+
+/// Returns the state of a `save` from the output printed so far.
+///
+/// Only whole lines count: a success line from [`SAVE_SUCCEEDED_LINES`] or a
+/// line starting with `Failure:`.
+#[must_use]
+pub fn save_status(output: &str) -> SaveStatus {
+    let mut status = SaveStatus::InProgress;
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("Failure:") {
+            return SaveStatus::Failed;
+        }
+        if SAVE_SUCCEEDED_LINES.contains(&line) {
+            status = SaveStatus::Succeeded;
+        }
+    }
+    status
+}
+
+/// Returns `true` for the first line of the configuration body, skipping the
+/// echoed command and its `Command:` banner.
+#[must_use]
+pub fn is_configuration_start(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('[') || line.starts_with('#')
+}
+
+/// Extracts the configuration body from `display current-configuration` output
+/// fed in chunks of any size, as they are read from the session.
+///
+/// Pager prompts are removed even when split across chunks, and the lines
+/// before the configuration body and after `return` are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigurationFilter {
+    pending: String,
+    pager_prompts: usize,
+    started: bool,
+    finished: bool,
+    failure: Option<String>,
+}
+
+impl ConfigurationFilter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a chunk of output and returns the configuration lines it completes.
+    pub fn push(&mut self, chunk: &str) -> Vec<String> {
+        self.pending.push_str(chunk);
+        if self.pending.contains(MORE_PROMPT) {
+            self.pager_prompts += self.pending.matches(MORE_PROMPT).count();
+            self.pending = self.pending.replace(MORE_PROMPT, "");
+        }
+
+        let mut lines = Vec::new();
+        while let Some(end) = self.pending.find('\n') {
+            let line = strip_pager(&self.pending[..end]);
+            self.pending.drain(..=end);
+            self.filter_line(&line, &mut lines);
+        }
+        lines
+    }
+
+    /// Returns the configuration line left without a trailing newline, if any.
+    pub fn finish(&mut self) -> Vec<String> {
+        let line = strip_pager(&std::mem::take(&mut self.pending));
+        let mut lines = Vec::new();
+        self.filter_line(&line, &mut lines);
+        lines
+    }
+
+    /// Returns `true` once for every pager prompt seen, which the OLT waits on
+    /// until it is answered.
+    pub const fn take_pager_prompt(&mut self) -> bool {
+        let waiting = self.pager_prompts > 0;
+        self.pager_prompts = self.pager_prompts.saturating_sub(1);
+        waiting
+    }
+
+    /// Output received after the last complete line, such as the prompt.
+    #[must_use]
+    pub fn pending(&self) -> &str {
+        &self.pending
+    }
+
+    /// Message of a `Failure:` line printed instead of the configuration.
+    #[must_use]
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+
+    fn filter_line(&mut self, line: &str, lines: &mut Vec<String>) {
+        if self.finished {
+            return;
+        }
+        if !self.started {
+            if let Some(msg) = line.trim().strip_prefix("Failure: ") {
+                self.failure = Some(msg.to_string());
+            }
+        }
+
+        self.started = self.started || is_configuration_start(line);
+        if !self.started {
+            return;
+        }
+        lines.push(line.trim_end().to_string());
+        self.finished = line.trim() == "return";
+    }
+}
+
+/// Extracts the configuration body from captured `display current-configuration`
+/// output.
+#[must_use]
+pub fn clean_configuration(output: &str) -> String {
+    let mut filter = ConfigurationFilter::new();
+    let mut lines = filter.push(output);
+    lines.extend(filter.finish());
+
+    let mut config = String::new();
+    for line in lines {
+        config.push_str(&line);
+        config.push('\n');
+    }
+    config
+}
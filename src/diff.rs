@@ -0,0 +1,192 @@
+//! Entity-level differences between two configuration or ONT summary snapshots.
+//!
+//! Entities are matched by key (VLAN ID, profile ID, F/S/P plus ONT ID,
+//! service-port index) and reported as added, removed or changed, ordered by key.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::{Fsp, ServicePort};
+use crate::ont_info_summary::{OntInfoSummary, OntInfoSummaryOnt};
+use crate::profile::{LineProfile, ServiceProfile};
+use crate::running_config::{
+    parse_running_config, ConfigOnt, ConfigPortVlan, ConfigVlan, RunningConfig,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One added, removed or changed entity
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct EntityChange<K, T> {
+    pub key: K,
+    pub kind: ChangeKind,
+    /// Entity in the earlier snapshot, `None` when added
+    pub before: Option<T>,
+    /// Entity in the later snapshot, `None` when removed
+    pub after: Option<T>,
+}
+
+/// Key of an ONT: its PON port and ONT ID
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct OntKey {
+    pub fsp: Fsp,
+    pub id: u32,
+}
+
+/// Differences between two `display current-configuration` captures
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub vlans: Vec<EntityChange<u32, ConfigVlan>>,
+    /// Uplink bindings are keyed by themselves and are only added or removed
+    pub port_vlans: Vec<EntityChange<ConfigPortVlan, ConfigPortVlan>>,
+    pub line_profiles: Vec<EntityChange<u32, LineProfile>>,
+    pub service_profiles: Vec<EntityChange<u32, ServiceProfile>>,
+    pub onts: Vec<EntityChange<OntKey, ConfigOnt>>,
+    /// Keyed by service-port index
+    pub service_ports: Vec<EntityChange<u32, ServicePort>>,
+}
+
+impl ConfigDiff {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.vlans.is_empty()
+            && self.port_vlans.is_empty()
+            && self.line_profiles.is_empty()
+            && self.service_profiles.is_empty()
+            && self.onts.is_empty()
+            && self.service_ports.is_empty()
+    }
+}
+
+/// Parses two `display current-configuration` captures and compares them.
+#[must_use]
+pub fn diff_configurations(before: &str, after: &str) -> ConfigDiff {
+    diff_running_configs(&parse_running_config(before), &parse_running_config(after))
+}
+
+#[must_use]
+pub fn diff_running_configs(before: &RunningConfig, after: &RunningConfig) -> ConfigDiff {
+    ConfigDiff {
+        vlans: diff_by_key(&before.vlans, &after.vlans, |v| v.id, PartialEq::eq),
+        port_vlans: diff_by_key(&before.port_vlans, &after.port_vlans, |p| *p, PartialEq::eq),
+        line_profiles: diff_by_key(
+            &before.line_profiles,
+            &after.line_profiles,
+            |p| p.id,
+            PartialEq::eq,
+        ),
+        service_profiles: diff_by_key(
+            &before.service_profiles,
+            &after.service_profiles,
+            |p| p.id,
+            PartialEq::eq,
+        ),
+        onts: diff_by_key(
+            &before.onts,
+            &after.onts,
+            |o| OntKey {
+                fsp: o.fsp,
+                id: o.id,
+            },
+            PartialEq::eq,
+        ),
+        service_ports: diff_by_key(
+            &before.service_ports,
+            &after.service_ports,
+            |sp| sp.index,
+            PartialEq::eq,
+        ),
+    }
+}
+
+/// Compares the ONTs of two `display ont info summary` snapshots.
+///
+/// An ONT counts as changed when its serial number, type, description or run
+/// state differs; optical levels, distance and timestamps are ignored.
+#[must_use]
+pub fn diff_ont_summaries(
+    before: &OntInfoSummary,
+    after: &OntInfoSummary,
+) -> Vec<EntityChange<OntKey, OntInfoSummaryOnt>> {
+    let flatten = |summary: &OntInfoSummary| -> Vec<(OntKey, OntInfoSummaryOnt)> {
+        summary
+            .ports
+            .iter()
+            .flat_map(|port| {
+                port.onts.iter().map(|ont| {
+                    (
+                        OntKey {
+                            fsp: port.fsp,
+                            id: ont.id,
+                        },
+                        ont.clone(),
+                    )
+                })
+            })
+            .collect()
+    };
+
+    diff_by_key(
+        &flatten(before),
+        &flatten(after),
+        |(key, _)| *key,
+        |(_, a), (_, b)| {
+            a.sn == b.sn
+                && a.ont_type == b.ont_type
+                && a.description == b.description
+                && a.run_state == b.run_state
+        },
+    )
+    .into_iter()
+    .map(|change| EntityChange {
+        key: change.key,
+        kind: change.kind,
+        before: change.before.map(|(_, ont)| ont),
+        after: change.after.map(|(_, ont)| ont),
+    })
+    .collect()
+}
+
+fn diff_by_key<K, T, F, S>(before: &[T], after: &[T], key: F, same: S) -> Vec<EntityChange<K, T>>
+where
+    K: Ord,
+    T: Clone,
+    F: Fn(&T) -> K,
+    S: Fn(&T, &T) -> bool,
+{
+    let mut entities: BTreeMap<K, (Option<&T>, Option<&T>)> = BTreeMap::new();
+    for item in before {
+        entities.entry(key(item)).or_default().0 = Some(item);
+    }
+    for item in after {
+        entities.entry(key(item)).or_default().1 = Some(item);
+    }
+
+    entities
+        .into_iter()
+        .filter_map(|(key, pair)| {
+            let kind = match pair {
+                (None, Some(_)) => ChangeKind::Added,
+                (Some(_), None) => ChangeKind::Removed,
+                (Some(a), Some(b)) if !same(a, b) => ChangeKind::Changed,
+                _ => return None,
+            };
+            Some(EntityChange {
+                key,
+                kind,
+                before: pair.0.cloned(),
+                after: pair.1.cloned(),
+            })
+        })
+        .collect()
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::migrate::MigrationFailure;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug)]
@@ -15,6 +17,9 @@ pub enum Error {
     InvalidMacAddress,
     InvalidContext(String),
     CommandFailed(String),
+    VlanNotFound(u32),
+    VlanWithoutUplink(u32),
+    MigrationFailed(Box<MigrationFailure>),
 }
 
 impl fmt::Display for Error {
@@ -31,6 +36,9 @@ impl fmt::Display for Error {
             Self::InvalidMacAddress => write!(f, "Invalid MAC address"),
             Self::InvalidContext(msg) => write!(f, "Invalid context: {msg}"),
             Self::CommandFailed(msg) => write!(f, "Command failed: {msg}"),
+            Self::VlanNotFound(vlan) => write!(f, "VLAN {vlan} does not exist"),
+            Self::VlanWithoutUplink(vlan) => write!(f, "VLAN {vlan} has no uplink port"),
+            Self::MigrationFailed(failure) => write!(f, "Migration failed: {failure}"),
         }
     }
 }
@@ -3,22 +3,88 @@
 //! A Rust library for interacting with Huawei MA5800 OLT (Optical Line Terminal) and similar devices.
 
 pub mod alarm;
+pub mod allocation;
+pub mod bulk;
+pub mod configuration;
+pub mod diff;
 pub mod error;
+pub mod migrate;
 pub mod models;
 pub mod ont_info_summary;
+pub mod ont_search;
+pub mod ont_statistics;
 pub mod parser;
+pub mod pon_port;
+pub mod profile;
+pub mod provisioner;
 pub mod r2d2;
+pub mod reconcile;
+pub mod running_config;
 pub mod ssh;
+pub mod system;
+pub mod traffic_table;
+pub mod vlan;
 
 pub use alarm::{parse_active_alarms_detail, parse_active_alarms_list, ActiveAlarm, ActiveAlarms};
+pub use allocation::{OntIdAllocator, ServicePortIndexAllocator, GPON_MAX_ONT_ID};
+pub use bulk::{BulkReport, Manifest, ManifestRow, RowIssue, RowResult, RowStatus};
+pub use configuration::{clean_configuration, save_status, ConfigurationFilter, SaveStatus};
+pub use diff::{
+    diff_configurations, diff_ont_summaries, diff_running_configs, ChangeKind, ConfigDiff,
+    EntityChange, OntKey,
+};
 pub use error::{Error, Result};
-pub use models::{BoardSlot, Fsp, OntAutofindEntry, OntInfo, OpticalInfo, ServicePort};
+pub use migrate::{MigrateOptions, MigrationFailure, MigrationReport, RollbackOutcome};
+pub use models::{
+    BoardDetail, BoardPort, BoardSlot, Fsp, NativeVlan, OntAutofindEntry, OntDownCause,
+    OntEthPortState, OntInfo, OntInfoList, OntInfoListEntry, OntIpConfig, OntOpticalLevels,
+    OntPortAttribute, OntPortType, OntRegisterRecord, OntVersion, OntWanInfo, OpticalInfo,
+    ServicePort, ServicePortTable,
+};
 pub use ont_info_summary::{
-    parse_ont_info_summary, OntInfoSummary, OntInfoSummaryOnt, OntInfoSummaryPort,
+    parse_ont_info_summary, BoardOntCount, OntInfoSummary, OntInfoSummaryOnt, OntInfoSummaryPort,
+};
+pub use ont_search::{
+    find_onts_by_description, search_summary, DescriptionQuery, OntSearchCache, OntSearchHit,
+    SearchTarget,
+};
+pub use ont_statistics::{
+    counter_rates, parse_ont_counters, parse_ont_traffic, OntCounterRates, OntCounters, OntTraffic,
 };
 pub use parser::{
-    parse_display_board, parse_ont_autofind, parse_ont_info, parse_optical_info,
+    normalize_serial_number, parse_board_detail, parse_display_board, parse_ont_autofind,
+    parse_ont_eth_port_states, parse_ont_info, parse_ont_info_list, parse_ont_ipconfig,
+    parse_ont_optical_levels, parse_ont_port_attributes, parse_ont_register_info,
+    parse_ont_versions, parse_ont_wan_info, parse_optical_info, parse_service_port_table,
     parse_service_ports,
 };
+pub use pon_port::{
+    parse_pon_optical_module, parse_pon_port_info, parse_pon_port_state, PonOpticalModule,
+    PonPortInfo, PonPortState,
+};
+pub use profile::{
+    dba_profile_add_command, parse_dba_profiles, parse_line_profile, parse_profile_summaries,
+    parse_service_profile, DbaBandwidth, DbaProfile, GemMapping, GemPort, LineProfile, PortCount,
+    ProfileSummary, ServiceProfile, ServiceProfilePort, Tcont,
+};
+pub use provisioner::Provisioner;
 pub use r2d2::ConnectionManager;
+pub use reconcile::{
+    ActionResult, DesiredOnt, DesiredServicePort, ObservedOnt, ObservedState, Plan, PlanAction,
+};
+pub use running_config::{
+    parse_running_config, ConfigOnt, ConfigPortVlan, ConfigVlan, RunningConfig,
+};
 pub use ssh::Connection;
+pub use system::{
+    parse_occupancy, parse_system_patches, parse_system_version, parse_temperature, parse_uptime,
+    BoardHealth, SystemInfo, SystemPatch, SystemVersion,
+};
+pub use traffic_table::{
+    find_traffic_table, free_traffic_table_index, parse_traffic_tables, PriorityPolicy,
+    TrafficTable, TrafficTableConfig,
+};
+pub use vlan::{
+    check_service_port_vlans, parse_vlan_detail, parse_vlans, Vlan, VlanDetail, VlanMemberPort,
+    VlanType,
+};
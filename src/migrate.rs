@@ -0,0 +1,329 @@
+//! Moving an ONT from one PON port to another.
+//!
+//! The ONT's description, profiles, Ethernet native VLANs and service ports are
+//! read from the source port, removed, recreated on the destination port, and the
+//! ONT is then polled until it comes online at the destination. A failure part
+//! way through puts the ONT back where it was.
+
+use std::fmt;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::{Error, Result};
+use crate::models::{Fsp, NativeVlan, OntInfo, OntPortType, ServicePort};
+use crate::provisioner::Provisioner;
+use crate::reconcile::{read_native_vlans, DesiredServicePort};
+
+#[derive(Debug, Clone)]
+pub struct MigrateOptions {
+    /// How long to wait for the ONT to come online at the destination
+    pub online_timeout: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for MigrateOptions {
+    fn default() -> Self {
+        Self {
+            online_timeout: Duration::from_secs(120),
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Serial number (raw)
+    pub sn: String,
+    pub source: Fsp,
+    pub source_ont_id: u32,
+    pub destination: Fsp,
+    pub destination_ont_id: u32,
+    /// Native VLANs recreated on the destination
+    pub native_vlans: Vec<NativeVlan>,
+    /// Service-port indexes removed from the source
+    pub removed_service_ports: Vec<u32>,
+    /// Service ports recreated on the destination
+    pub service_ports: Vec<DesiredServicePort>,
+    /// Whether the ONT reported `online` at the destination before the timeout
+    pub online: bool,
+}
+
+/// What was left behind by a migration that failed after changing the OLT.
+#[derive(Debug)]
+pub struct MigrationFailure {
+    /// Serial number (raw)
+    pub sn: String,
+    pub source: Fsp,
+    pub source_ont_id: u32,
+    pub destination: Fsp,
+    /// The error that stopped the migration
+    pub error: Error,
+    /// Service-port indexes that had been removed from the source
+    pub removed_service_ports: Vec<u32>,
+    /// ONT ID assigned at the destination, if the ONT was added there
+    pub destination_ont_id: Option<u32>,
+    pub rollback: RollbackOutcome,
+}
+
+#[derive(Debug)]
+pub enum RollbackOutcome {
+    /// The ONT, its native VLANs and service ports are back on the source port
+    Restored,
+    /// Restoring the source failed; the OLT needs manual attention
+    Failed(Error),
+}
+
+impl fmt::Display for MigrationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from {} to {}: {}; removed service ports {:?}",
+            self.sn, self.source, self.destination, self.error, self.removed_service_ports
+        )?;
+        if let Some(id) = self.destination_ont_id {
+            write!(f, "; added as ONT {id} on {}", self.destination)?;
+        }
+        match &self.rollback {
+            RollbackOutcome::Restored => write!(f, "; restored on {}", self.source),
+            RollbackOutcome::Failed(err) => write!(f, "; rollback failed: {err}"),
+        }
+    }
+}
+
+/// Migrates the ONT with serial number `sn` to `destination`.
+///
+/// Once the source has been changed, by removing its first service port or by
+/// deleting an ONT that has none, any failure rolls the migration back: whatever
+/// was created at the destination is removed and the ONT is restored on the
+/// source port with its original ID, native VLANs and service ports. The result is returned as [`Error::MigrationFailed`].
+pub fn migrate_ont(
+    conn: &mut impl Provisioner,
+    sn: &str,
+    destination: Fsp,
+    options: &MigrateOptions,
+) -> Result<MigrationReport> {
+    conn.ensure_config()?;
+
+    let info = conn.display_ont_info_by_sn(sn)?.ok_or(Error::NotFound)?;
+    if info.fsp == destination {
+        return Err(Error::CommandFailed(format!(
+            "ONT {} is already on {destination}",
+            info.sn
+        )));
+    }
+
+    let flows = conn.display_service_port(info.fsp, info.id)?;
+    let service_ports = flows
+        .iter()
+        .map(|flow| {
+            DesiredServicePort::from_service_port(flow).ok_or_else(|| {
+                Error::CommandFailed(format!(
+                    "Service port {} cannot be recreated on {destination}",
+                    flow.index
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let native_vlans = read_native_vlans(conn, info.fsp, info.id, &[OntPortType::Eth])?;
+
+    let vlans: Vec<u32> = service_ports.iter().map(|sp| sp.vlan).collect();
+    conn.ensure_config()?;
+    conn.check_service_port_vlans(&vlans)?;
+
+    let mut migration = Migration {
+        info: &info,
+        destination,
+        native_vlans: &native_vlans,
+        service_ports: &service_ports,
+        removed_service_ports: Vec::new(),
+        source_deleted: false,
+        destination_ont_id: None,
+    };
+
+    match migration.run(conn, &flows, options) {
+        Ok(online) => Ok(MigrationReport {
+            sn: info.sn.clone(),
+            source: info.fsp,
+            source_ont_id: info.id,
+            destination,
+            destination_ont_id: migration.destination_ont_id.unwrap_or_default(),
+            native_vlans: native_vlans.clone(),
+            removed_service_ports: migration.removed_service_ports,
+            service_ports: service_ports.clone(),
+            online,
+        }),
+        Err(err) if migration.removed_service_ports.is_empty() && !migration.source_deleted => {
+            Err(err)
+        }
+        Err(error) => {
+            let rollback = match migration.roll_back(conn) {
+                Ok(()) => RollbackOutcome::Restored,
+                Err(err) => RollbackOutcome::Failed(err),
+            };
+            Err(Error::MigrationFailed(Box::new(MigrationFailure {
+                sn: info.sn.clone(),
+                source: info.fsp,
+                source_ont_id: info.id,
+                destination,
+                error,
+                removed_service_ports: migration.removed_service_ports,
+                destination_ont_id: migration.destination_ont_id,
+                rollback,
+            })))
+        }
+    }
+}
+
+/// Progress of a migration, used to undo exactly what was done.
+struct Migration<'a> {
+    info: &'a OntInfo,
+    destination: Fsp,
+    native_vlans: &'a [NativeVlan],
+    service_ports: &'a [DesiredServicePort],
+    removed_service_ports: Vec<u32>,
+    source_deleted: bool,
+    destination_ont_id: Option<u32>,
+}
+
+impl Migration<'_> {
+    fn run(
+        &mut self,
+        conn: &mut impl Provisioner,
+        flows: &[ServicePort],
+        options: &MigrateOptions,
+    ) -> Result<bool> {
+        let source = self.info.fsp;
+
+        conn.ensure_config()?;
+        for flow in flows {
+            conn.service_port_undo(flow.index)?;
+            self.removed_service_ports.push(flow.index);
+        }
+
+        conn.ensure_interface_gpon(source.frame, source.slot)?;
+        conn.ont_delete(source.port, self.info.id)?;
+        self.source_deleted = true;
+
+        let ont_id = add_ont(conn, self.info, self.destination, None)?;
+        self.destination_ont_id = Some(ont_id);
+        configure_ont(
+            conn,
+            self.destination,
+            ont_id,
+            self.native_vlans,
+            self.service_ports,
+        )?;
+
+        wait_online(conn, &self.info.sn, self.destination, options)
+    }
+
+    fn roll_back(&self, conn: &mut impl Provisioner) -> Result<()> {
+        let source = self.info.fsp;
+
+        if let Some(ont_id) = self.destination_ont_id {
+            conn.ensure_config()?;
+            for flow in conn.display_service_port(self.destination, ont_id)? {
+                conn.service_port_undo(flow.index)?;
+            }
+            conn.ensure_interface_gpon(self.destination.frame, self.destination.slot)?;
+            conn.ont_delete(self.destination.port, ont_id)?;
+        }
+
+        if self.source_deleted {
+            add_ont(conn, self.info, source, Some(self.info.id))?;
+            return configure_ont(
+                conn,
+                source,
+                self.info.id,
+                self.native_vlans,
+                self.service_ports,
+            );
+        }
+
+        conn.ensure_config()?;
+        for service_port in &self.service_ports[..self.removed_service_ports.len()] {
+            conn.service_port_add_unchecked(&service_port.to_config(source, self.info.id)?)?;
+        }
+        Ok(())
+    }
+}
+
+fn add_ont(
+    conn: &mut impl Provisioner,
+    info: &OntInfo,
+    fsp: Fsp,
+    ont_id: Option<u32>,
+) -> Result<u32> {
+    conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+    match ont_id {
+        Some(ont_id) => conn.ont_add_with_id(
+            fsp.port,
+            ont_id,
+            &info.sn,
+            &info.description,
+            info.line_profile_id,
+            info.service_profile_id,
+        ),
+        None => conn.ont_add(
+            fsp.port,
+            &info.sn,
+            &info.description,
+            info.line_profile_id,
+            info.service_profile_id,
+        ),
+    }
+}
+
+fn configure_ont(
+    conn: &mut impl Provisioner,
+    fsp: Fsp,
+    ont_id: u32,
+    native_vlans: &[NativeVlan],
+    service_ports: &[DesiredServicePort],
+) -> Result<()> {
+    conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+    for native_vlan in native_vlans {
+        conn.ont_port_native_vlan(
+            fsp.port,
+            ont_id,
+            native_vlan.port_type,
+            native_vlan.ont_port,
+            native_vlan.vlan,
+            native_vlan.priority,
+        )?;
+    }
+
+    conn.ensure_config()?;
+    for service_port in service_ports {
+        conn.service_port_add_unchecked(&service_port.to_config(fsp, ont_id)?)?;
+    }
+
+    Ok(())
+}
+
+fn wait_online(
+    conn: &mut impl Provisioner,
+    sn: &str,
+    destination: Fsp,
+    options: &MigrateOptions,
+) -> Result<bool> {
+    conn.ensure_config()?;
+    let start = Instant::now();
+
+    loop {
+        if let Some(info) = conn.display_ont_info_by_sn(sn)? {
+            if info.fsp == destination && info.run_state == "online" {
+                return Ok(true);
+            }
+        }
+
+        if start.elapsed() >= options.online_timeout {
+            return Ok(false);
+        }
+        sleep(options.poll_interval);
+    }
+}
@@ -24,7 +24,7 @@ pub struct OntAutofindEntry {
     pub auto_find_time: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
 pub struct OntInfo {
     /// Frame/Slot/Port
     pub fsp: Fsp,
@@ -51,7 +51,8 @@ pub struct OntInfo {
     pub sn_readable: String,
     pub management_mode: String,
     pub description: String,
-    pub last_down_cause: String,
+    #[schema(value_type = String)]
+    pub last_down_cause: OntDownCause,
     pub last_up_time: String,
     pub last_down_time: String,
     pub online_duration: String,
@@ -94,10 +95,342 @@ pub struct OpticalInfo {
     pub catv_rx_power_alarm_threshold: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+/// Ethernet port state from `display ont port state ... eth-port all`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OntEthPortState {
+    pub ont_id: u32,
+    pub port_id: u32,
+    /// Port type (e.g. `GE`, `FE`)
+    pub port_type: String,
+    /// Negotiated speed in Mbit/s, `None` when the link is down
+    pub speed_mbps: Option<u32>,
+    /// `full` or `half`, `None` when the link is down
+    pub duplex: Option<String>,
+    /// `up` or `down`
+    pub link_state: String,
+    pub ring_status: String,
+}
+
+impl OntEthPortState {
+    #[must_use]
+    pub fn is_link_up(&self) -> bool {
+        self.link_state.eq_ignore_ascii_case("up")
+    }
+}
+
+/// ONT hardware and software versions from `display ont version`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OntVersion {
+    /// Frame/Slot/Port
+    pub fsp: Fsp,
+    pub ont_id: u32,
+    pub vendor_id: String,
+    /// Hardware version
+    pub ont_version: String,
+    pub product_description: String,
+    pub equipment_id: String,
+    pub main_software_version: String,
+    pub standby_software_version: Option<String>,
+    pub oui_version: Option<String>,
+}
+
+/// One WAN connection from `display ont wan-info`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OntWanInfo {
+    pub index: u32,
+    pub name: String,
+    /// Service type (e.g. `Internet`, `TR069`, `VOIP`)
+    pub service_type: String,
+    /// Connection type (e.g. `IP routed`, `IP bridged`)
+    pub connection_type: String,
+    /// IPv4 connection status (e.g. `Connected`)
+    pub ipv4_status: String,
+    /// IPv4 access type (e.g. `DHCP`, `PPPoE`, `Static`)
+    pub ipv4_access_type: String,
+    pub ipv4_address: Option<String>,
+    pub subnet_mask: Option<String>,
+    pub gateway: Option<String>,
+    pub primary_dns: Option<String>,
+    pub secondary_dns: Option<String>,
+    pub vlan: Option<u32>,
+    pub mac_address: Option<String>,
+    pub pppoe_username: Option<String>,
+    pub ipv6_status: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub ipv6_prefix: Option<String>,
+}
+
+impl OntWanInfo {
+    /// Whether the WAN is connected and holds an IPv4 address.
+    #[must_use]
+    pub fn has_ipv4(&self) -> bool {
+        self.ipv4_status.eq_ignore_ascii_case("connected") && self.ipv4_address.is_some()
+    }
+}
+
+/// ONT management IP configuration from `display ont ipconfig`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OntIpConfig {
+    pub index: u32,
+    /// Config type (e.g. `DHCP`, `Static`)
+    pub config_type: String,
+    pub ip_address: Option<String>,
+    pub subnet_mask: Option<String>,
+    pub gateway: Option<String>,
+    pub primary_dns: Option<String>,
+    pub secondary_dns: Option<String>,
+    pub mac_address: Option<String>,
+    pub vlan: Option<u32>,
+    pub priority: Option<u32>,
+}
+
+/// Optical levels of one ONT from `display ont optical-info <port> all`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct OntOpticalLevels {
+    pub ont_id: u32,
+    /// ONT Rx optical power in dBm
+    pub rx_power: Option<f32>,
+    /// ONT Tx optical power in dBm
+    pub tx_power: Option<f32>,
+    /// OLT Rx optical power from this ONT in dBm
+    pub olt_rx_power: Option<f32>,
+    /// Temperature in Celsius
+    pub temperature: Option<i32>,
+    /// Supply voltage in V
+    pub voltage: Option<f32>,
+    /// Laser bias current in mA
+    pub bias_current: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
 pub struct ServicePort {
     pub index: u32,
     pub vlan: u32,
+    /// VLAN attribute (e.g. `common`, `stacking`)
+    pub vlan_attr: String,
+    /// Port type (e.g. `gpon`, `eth`)
+    pub port_type: String,
+    /// Frame/Slot/Port
+    pub fsp: Fsp,
+    /// VPI, the ONT ID for PON ports
+    pub ont_id: Option<u32>,
+    /// VCI, the GEM index for GPON ports
+    pub gemport: Option<u32>,
+    /// Flow type (e.g. `vlan`, `encap`, `pritag`)
+    pub flow_type: String,
+    /// Flow parameter (the user VLAN when the flow type is `vlan`)
+    pub flow_para: String,
+    /// Inbound (RX) traffic table index
+    pub inbound_traffic_table: Option<u32>,
+    /// Outbound (TX) traffic table index
+    pub outbound_traffic_table: Option<u32>,
+    /// `up` or `down`
+    pub state: String,
+}
+
+impl ServicePort {
+    /// Returns the user VLAN when the flow type is `vlan`.
+    #[must_use]
+    pub fn user_vlan(&self) -> Option<u32> {
+        if self.flow_type == "vlan" {
+            self.flow_para.parse().ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// Service-port table with the `Total : N (Up/Down : x/y)` footer
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct ServicePortTable {
+    pub ports: Vec<ServicePort>,
+    pub total: u32,
+    pub up: u32,
+    pub down: u32,
+}
+
+/// Reason an ONT went offline, as printed in `DownCause` columns
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum OntDownCause {
+    /// No down cause recorded (`-` or empty)
+    #[default]
+    Unknown,
+    /// `dying-gasp`: the ONT lost power
+    DyingGasp,
+    /// `LOSi/LOBi`: loss of signal and burst
+    LosiLobi,
+    /// `LOSi`: loss of signal
+    Losi,
+    /// `LOBi`: loss of burst
+    Lobi,
+    /// `LOFi`: loss of frame
+    Lofi,
+    /// `SFi`: signal fail
+    Sfi,
+    /// `LOAi`: loss of acknowledgement
+    Loai,
+    /// `LOAMi`: loss of PLOAM
+    Loami,
+    /// Any other cause, kept verbatim
+    Other(String),
+}
+
+impl OntDownCause {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Unknown => "",
+            Self::DyingGasp => "dying-gasp",
+            Self::LosiLobi => "LOSi/LOBi",
+            Self::Losi => "LOSi",
+            Self::Lobi => "LOBi",
+            Self::Lofi => "LOFi",
+            Self::Sfi => "SFi",
+            Self::Loai => "LOAi",
+            Self::Loami => "LOAMi",
+            Self::Other(cause) => cause,
+        }
+    }
+
+    /// Whether the cause points at the optical path rather than the subscriber's power.
+    #[must_use]
+    pub const fn is_optical(&self) -> bool {
+        matches!(
+            self,
+            Self::LosiLobi | Self::Losi | Self::Lobi | Self::Lofi | Self::Sfi
+        )
+    }
+}
+
+impl From<&str> for OntDownCause {
+    fn from(value: &str) -> Self {
+        match value.trim() {
+            "" | "-" => Self::Unknown,
+            "dying-gasp" => Self::DyingGasp,
+            "LOSi/LOBi" => Self::LosiLobi,
+            "LOSi" => Self::Losi,
+            "LOBi" => Self::Lobi,
+            "LOFi" => Self::Lofi,
+            "SFi" => Self::Sfi,
+            "LOAi" => Self::Loai,
+            "LOAMi" => Self::Loami,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for OntDownCause {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<OntDownCause> for String {
+    fn from(value: OntDownCause) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for OntDownCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for OntDownCause {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for OntDownCause {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// One entry of `display ont register-info`, newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OntRegisterRecord {
+    pub index: u32,
+    /// Authentication type (e.g. `SN`, `LOID`)
+    pub auth_type: String,
+    /// Serial number (raw)
+    pub sn: String,
+    pub up_time: Option<String>,
+    pub down_time: Option<String>,
+    #[schema(value_type = String)]
+    pub down_cause: OntDownCause,
+}
+
+/// ONT port types accepted by `ont port` commands
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OntPortType {
+    Eth,
+    Pots,
+    Catv,
+    Wifi,
+}
+
+impl OntPortType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Eth => "eth",
+            Self::Pots => "pots",
+            Self::Catv => "catv",
+            Self::Wifi => "wifi",
+        }
+    }
+}
+
+impl std::fmt::Display for OntPortType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for OntPortType {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "eth" => Ok(Self::Eth),
+            "pots" => Ok(Self::Pots),
+            "catv" => Ok(Self::Catv),
+            "wifi" => Ok(Self::Wifi),
+            _ => Err(crate::error::Error::ParseError(format!(
+                "Unknown ONT port type: {s}"
+            ))),
+        }
+    }
+}
+
+/// Native VLAN of an ONT port, set by `ont port native-vlan`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct NativeVlan {
+    pub port_type: OntPortType,
+    /// ONT port number, starting at 1
+    pub ont_port: u32,
+    pub vlan: u32,
+    pub priority: u32,
+}
+
+/// Row of `display ont port attribute`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OntPortAttribute {
+    pub ont_id: u32,
+    pub port_id: u32,
+    /// Port type as printed by the OLT, e.g. `GE` or `FE`
+    pub port_type: String,
+    pub speed: String,
+    pub duplex: String,
+    pub native_vlan: Option<u32>,
+    pub priority: Option<u32>,
+    pub flow_control: String,
+    pub active_state: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
@@ -110,8 +443,89 @@ pub struct BoardSlot {
     pub online_status: Option<String>,
 }
 
+/// Board detail reported by `display board F/S`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct BoardDetail {
+    /// Board type (e.g. `H901GPUF`)
+    pub board_name: String,
+    pub status: String,
+    pub online_state: Option<String>,
+    pub ports: Vec<BoardPort>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct BoardPort {
+    pub port: u32,
+    /// Port type (e.g. `GPON`)
+    pub port_type: String,
+    /// `Enable` or `Disable`
+    pub port_status: String,
+    pub min_distance_km: Option<u32>,
+    pub max_distance_km: Option<u32>,
+    /// `Online` when an optical module is inserted
+    pub optical_module_status: String,
+    /// Number of ONTs configured on the port
+    pub ont_total: Option<u32>,
+    /// Number of ONTs online on the port
+    pub ont_online: Option<u32>,
+}
+
+impl BoardPort {
+    #[must_use]
+    pub fn has_optical_module(&self) -> bool {
+        self.optical_module_status.eq_ignore_ascii_case("online")
+    }
+}
+
+/// ONTs of one PON port as reported by `display ont info F/S/P all`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+pub struct OntInfoList {
+    pub fsp: Fsp,
+    pub total_onts: u32,
+    pub online_onts: u32,
+    pub onts: Vec<OntInfoListEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct OntInfoListEntry {
+    pub fsp: Fsp,
+    pub id: u32,
+    /// Serial number (raw)
+    pub sn: String,
+    pub control_flag: String,
+    pub run_state: String,
+    pub config_state: String,
+    pub match_state: String,
+    pub protect_side: String,
+    pub description: String,
+}
+
+impl OntInfoList {
+    /// ONTs whose config state is not `normal` or whose match state is not `match`.
+    #[must_use]
+    pub fn config_mismatches(&self) -> Vec<&OntInfoListEntry> {
+        self.onts
+            .iter()
+            .filter(|o| o.config_state != "normal" || o.match_state != "match")
+            .collect()
+    }
+}
+
 /// Frame/Slot/Port representation
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, Default)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+)]
 pub struct Fsp {
     pub frame: u32,
     pub slot: u32,
@@ -121,7 +535,7 @@ pub struct Fsp {
 impl Fsp {
     #[must_use]
     pub fn parse(fsp: &str) -> Option<Self> {
-        let parts: Vec<&str> = fsp.split('/').collect();
+        let parts: Vec<&str> = fsp.split('/').map(str::trim).collect();
         if parts.len() != 3 {
             return None;
         }
@@ -1,7 +1,7 @@
-use crate::models::Fsp;
+use crate::models::{Fsp, OntDownCause};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
@@ -23,7 +23,8 @@ pub struct OntInfoSummaryOnt {
     pub run_state: String,
     pub last_up_time: String,
     pub last_down_time: String,
-    pub last_down_cause: String,
+    #[schema(value_type = String)]
+    pub last_down_cause: OntDownCause,
     pub sn: String,
     pub ont_type: String,
     pub distance_m: Option<u32>,
@@ -32,6 +33,64 @@ pub struct OntInfoSummaryOnt {
     pub description: String,
 }
 
+/// ONT counts of one board, aggregated from its ports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct BoardOntCount {
+    pub frame: u32,
+    pub slot: u32,
+    /// Number of PON ports with at least one ONT
+    pub ports: u32,
+    pub total_onts: u32,
+    pub online_onts: u32,
+}
+
+impl OntInfoSummary {
+    #[must_use]
+    pub fn port(&self, fsp: Fsp) -> Option<&OntInfoSummaryPort> {
+        self.ports.iter().find(|p| p.fsp == fsp)
+    }
+
+    /// Total ONTs over all ports, as reported in the port headers.
+    #[must_use]
+    pub fn total_onts(&self) -> u32 {
+        self.ports.iter().map(|p| p.total_onts).sum()
+    }
+
+    #[must_use]
+    pub fn online_onts(&self) -> u32 {
+        self.ports.iter().map(|p| p.online_onts).sum()
+    }
+
+    #[must_use]
+    pub fn offline_onts(&self) -> u32 {
+        self.total_onts().saturating_sub(self.online_onts())
+    }
+
+    /// Returns the ONT counts per board, ordered by frame and slot.
+    ///
+    /// Boards whose ports are all empty are listed with `ports` at zero.
+    #[must_use]
+    pub fn boards(&self) -> Vec<BoardOntCount> {
+        let mut boards: BTreeMap<(u32, u32), BoardOntCount> = BTreeMap::new();
+        for port in &self.ports {
+            let board = boards
+                .entry((port.fsp.frame, port.fsp.slot))
+                .or_insert_with(|| BoardOntCount {
+                    frame: port.fsp.frame,
+                    slot: port.fsp.slot,
+                    ..BoardOntCount::default()
+                });
+            if port.total_onts > 0 {
+                board.ports += 1;
+            }
+            board.total_onts += port.total_onts;
+            board.online_onts += port.online_onts;
+        }
+
+        boards.into_values().collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SummarySection {
     None,
@@ -151,7 +210,7 @@ struct StateFields {
     run_state: String,
     last_up_time: String,
     last_down_time: String,
-    last_down_cause: String,
+    last_down_cause: OntDownCause,
 }
 
 struct OntFields {
@@ -175,9 +234,9 @@ fn parse_state_line(line: &str) -> Option<StateFields> {
     let last_up_time = format!("{} {}", parts[2], parts[3]);
     let last_down_time = format!("{} {}", parts[4], parts[5]);
     let last_down_cause = if parts.len() > 6 {
-        parts[6..].join(" ")
+        OntDownCause::from(parts[6..].join(" "))
     } else {
-        String::new()
+        OntDownCause::Unknown
     };
 
     Some(StateFields {
@@ -0,0 +1,252 @@
+//! Finding ONTs by description.
+//!
+//! Support staff know customers by the account or e-mail stored in the ONT
+//! description. `display ont info by-desc` only finds exact descriptions, so
+//! [`OntSearchCache`] keeps `display ont info summary` snapshots of whole frames
+//! or a set of ports and searches them by substring or regular expression.
+
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::{Error, Result};
+use crate::models::Fsp;
+use crate::models::OntInfoListEntry;
+use crate::ont_info_summary::OntInfoSummary;
+use crate::provisioner::Provisioner;
+use crate::ssh::Connection;
+
+/// ONT found by a description search
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct OntSearchHit {
+    pub fsp: Fsp,
+    pub id: u32,
+    /// Serial number (raw)
+    pub sn: String,
+    pub run_state: String,
+    pub description: String,
+}
+
+impl From<&OntInfoListEntry> for OntSearchHit {
+    fn from(entry: &OntInfoListEntry) -> Self {
+        Self {
+            fsp: entry.fsp,
+            id: entry.id,
+            sn: entry.sn.clone(),
+            run_state: entry.run_state.clone(),
+            description: entry.description.clone(),
+        }
+    }
+}
+
+/// How a description is matched
+#[derive(Debug, Clone)]
+pub enum DescriptionQuery {
+    /// Case-insensitive substring
+    Substring(String),
+    Regex(Regex),
+}
+
+impl DescriptionQuery {
+    #[must_use]
+    pub fn substring(text: &str) -> Self {
+        Self::Substring(text.to_string())
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self> {
+        Regex::new(pattern)
+            .map(Self::Regex)
+            .map_err(|e| Error::ParseError(format!("Invalid description pattern: {e}")))
+    }
+
+    #[must_use]
+    pub fn matches(&self, description: &str) -> bool {
+        match self {
+            Self::Substring(text) => description.to_lowercase().contains(&text.to_lowercase()),
+            Self::Regex(re) => re.is_match(description),
+        }
+    }
+}
+
+/// Searches the ONTs of a summary.
+///
+/// Summary descriptions are truncated by the OLT, so matches near the end of
+/// long descriptions can be missed.
+#[must_use]
+pub fn search_summary(summary: &OntInfoSummary, query: &DescriptionQuery) -> Vec<OntSearchHit> {
+    summary
+        .ports
+        .iter()
+        .flat_map(|port| {
+            port.onts
+                .iter()
+                .filter(|ont| query.matches(&ont.description))
+                .map(|ont| OntSearchHit {
+                    fsp: port.fsp,
+                    id: ont.id,
+                    sn: ont.sn.clone(),
+                    run_state: ont.run_state.clone(),
+                    description: ont.description.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Ports read by one `display ont info summary` of an [`OntSearchCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchTarget {
+    /// Every port of a frame
+    Frame(u32),
+    Port(Fsp),
+}
+
+impl std::fmt::Display for SearchTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Frame(frame) => write!(f, "{frame}"),
+            Self::Port(fsp) => write!(f, "{fsp}"),
+        }
+    }
+}
+
+/// `display ont info summary` snapshots of whole frames or a set of ports,
+/// refreshed once they are older than the TTL
+#[derive(Debug, Clone)]
+pub struct OntSearchCache {
+    targets: Vec<SearchTarget>,
+    ttl: Duration,
+    snapshot: Option<(Instant, OntInfoSummary)>,
+    /// Targets the last refresh could not read, with the error
+    skipped: Vec<(SearchTarget, String)>,
+}
+
+impl OntSearchCache {
+    /// Creates a cache covering the given ports.
+    #[must_use]
+    pub fn new(ports: Vec<Fsp>, ttl: Duration) -> Self {
+        Self::with_targets(ports.into_iter().map(SearchTarget::Port).collect(), ttl)
+    }
+
+    /// Creates a cache covering every port of the given frames, read with one
+    /// frame-level summary each.
+    #[must_use]
+    pub fn for_frames(frames: Vec<u32>, ttl: Duration) -> Self {
+        Self::with_targets(frames.into_iter().map(SearchTarget::Frame).collect(), ttl)
+    }
+
+    #[must_use]
+    pub const fn with_targets(targets: Vec<SearchTarget>, ttl: Duration) -> Self {
+        Self {
+            targets,
+            ttl,
+            snapshot: None,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Creates a cache preloaded with `summary`, covering its ports.
+    #[must_use]
+    pub fn from_summary(summary: OntInfoSummary, ttl: Duration) -> Self {
+        Self {
+            targets: summary
+                .ports
+                .iter()
+                .map(|p| SearchTarget::Port(p.fsp))
+                .collect(),
+            ttl,
+            snapshot: Some((Instant::now(), summary)),
+            skipped: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.snapshot
+            .as_ref()
+            .is_none_or(|(taken, _)| taken.elapsed() >= self.ttl)
+    }
+
+    pub fn invalidate(&mut self) {
+        self.snapshot = None;
+    }
+
+    /// Targets left out of the current snapshot because reading them failed.
+    #[must_use]
+    pub fn skipped(&self) -> &[(SearchTarget, String)] {
+        &self.skipped
+    }
+
+    /// Reloads the summary of every cached target.
+    ///
+    /// Targets that fail are skipped and listed by [`Self::skipped`]; the
+    /// refresh only fails, keeping the previous snapshot, when none could be
+    /// read.
+    pub fn refresh(&mut self, conn: &mut impl Provisioner) -> Result<()> {
+        let mut summary = OntInfoSummary::default();
+        let mut skipped = Vec::new();
+        let mut last_error = None;
+
+        for &target in &self.targets {
+            let result = match target {
+                SearchTarget::Frame(frame) => conn.display_ont_info_summary_frame(frame),
+                SearchTarget::Port(fsp) => {
+                    conn.display_ont_info_summary(fsp.frame, fsp.slot, fsp.port)
+                }
+            };
+            match result {
+                Ok(target_summary) => summary.ports.extend(target_summary.ports),
+                Err(e) => {
+                    skipped.push((target, e.to_string()));
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if skipped.len() == self.targets.len() {
+            if let Some(e) = last_error {
+                return Err(e);
+            }
+        }
+        self.snapshot = Some((Instant::now(), summary));
+        self.skipped = skipped;
+        Ok(())
+    }
+
+    /// Searches the cached summaries, refreshing them first if stale.
+    pub fn search(
+        &mut self,
+        conn: &mut impl Provisioner,
+        query: &DescriptionQuery,
+    ) -> Result<Vec<OntSearchHit>> {
+        if self.is_stale() {
+            self.refresh(conn)?;
+        }
+        Ok(self.search_cached(query))
+    }
+
+    /// Searches the cached summaries without contacting the OLT.
+    #[must_use]
+    pub fn search_cached(&self, query: &DescriptionQuery) -> Vec<OntSearchHit> {
+        self.snapshot
+            .as_ref()
+            .map(|(_, summary)| search_summary(summary, query))
+            .unwrap_or_default()
+    }
+}
+
+/// Looks up ONTs whose description is exactly `description` with
+/// `display ont info by-desc`, falling back to a substring search of `cache`.
+pub fn find_onts_by_description(
+    conn: &mut Connection,
+    cache: &mut OntSearchCache,
+    description: &str,
+) -> Result<Vec<OntSearchHit>> {
+    let exact = conn.display_ont_info_by_desc(description)?;
+    if !exact.is_empty() {
+        return Ok(exact.iter().map(OntSearchHit::from).collect());
+    }
+
+    cache.search(conn, &DescriptionQuery::substring(description))
+}
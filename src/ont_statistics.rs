@@ -0,0 +1,200 @@
+//! ONT traffic and line-quality counters.
+//!
+//! Counters are cumulative on the OLT, so [`counter_rates`] turns two samples
+//! taken some time apart into per-second rates.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::parser::key_values;
+
+/// Cumulative counters from `display statistics ont` and
+/// `display statistics ont-line-quality`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct OntCounters {
+    pub upstream_frames: Option<u64>,
+    pub upstream_bytes: Option<u64>,
+    pub upstream_dropped_frames: Option<u64>,
+    pub downstream_frames: Option<u64>,
+    pub downstream_bytes: Option<u64>,
+    pub downstream_dropped_frames: Option<u64>,
+    pub upstream_bip_errors: Option<u64>,
+    pub downstream_bip_errors: Option<u64>,
+    pub upstream_fec_corrected_bytes: Option<u64>,
+    pub upstream_fec_corrected_codewords: Option<u64>,
+    pub upstream_fec_uncorrectable_codewords: Option<u64>,
+    pub downstream_fec_corrected_bytes: Option<u64>,
+    pub downstream_fec_corrected_codewords: Option<u64>,
+    pub downstream_fec_uncorrectable_codewords: Option<u64>,
+    pub gem_hec_errors: Option<u64>,
+}
+
+impl OntCounters {
+    /// Fills the counters missing from `self` with those of `other`.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            upstream_frames: self.upstream_frames.or(other.upstream_frames),
+            upstream_bytes: self.upstream_bytes.or(other.upstream_bytes),
+            upstream_dropped_frames: self
+                .upstream_dropped_frames
+                .or(other.upstream_dropped_frames),
+            downstream_frames: self.downstream_frames.or(other.downstream_frames),
+            downstream_bytes: self.downstream_bytes.or(other.downstream_bytes),
+            downstream_dropped_frames: self
+                .downstream_dropped_frames
+                .or(other.downstream_dropped_frames),
+            upstream_bip_errors: self.upstream_bip_errors.or(other.upstream_bip_errors),
+            downstream_bip_errors: self.downstream_bip_errors.or(other.downstream_bip_errors),
+            upstream_fec_corrected_bytes: self
+                .upstream_fec_corrected_bytes
+                .or(other.upstream_fec_corrected_bytes),
+            upstream_fec_corrected_codewords: self
+                .upstream_fec_corrected_codewords
+                .or(other.upstream_fec_corrected_codewords),
+            upstream_fec_uncorrectable_codewords: self
+                .upstream_fec_uncorrectable_codewords
+                .or(other.upstream_fec_uncorrectable_codewords),
+            downstream_fec_corrected_bytes: self
+                .downstream_fec_corrected_bytes
+                .or(other.downstream_fec_corrected_bytes),
+            downstream_fec_corrected_codewords: self
+                .downstream_fec_corrected_codewords
+                .or(other.downstream_fec_corrected_codewords),
+            downstream_fec_uncorrectable_codewords: self
+                .downstream_fec_uncorrectable_codewords
+                .or(other.downstream_fec_uncorrectable_codewords),
+            gem_hec_errors: self.gem_hec_errors.or(other.gem_hec_errors),
+        }
+    }
+}
+
+/// Per-second rates computed from two [`OntCounters`] samples.
+///
+/// A rate is `None` when either sample lacks the counter or the counter went
+/// backwards (e.g. it was cleared between samples).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq)]
+pub struct OntCounterRates {
+    /// Upstream throughput in bits per second
+    pub upstream_bps: Option<f64>,
+    /// Downstream throughput in bits per second
+    pub downstream_bps: Option<f64>,
+    pub upstream_frames_per_second: Option<f64>,
+    pub downstream_frames_per_second: Option<f64>,
+    pub upstream_bip_errors_per_second: Option<f64>,
+    pub downstream_bip_errors_per_second: Option<f64>,
+    pub upstream_fec_corrected_per_second: Option<f64>,
+    pub downstream_fec_corrected_per_second: Option<f64>,
+    pub gem_hec_errors_per_second: Option<f64>,
+}
+
+/// Instantaneous traffic from `display ont traffic`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct OntTraffic {
+    pub ont_id: u32,
+    pub upstream_kbps: u64,
+    pub downstream_kbps: u64,
+}
+
+/// Turns two samples taken `elapsed` apart into per-second rates.
+#[must_use]
+pub fn counter_rates(
+    earlier: &OntCounters,
+    later: &OntCounters,
+    elapsed: Duration,
+) -> OntCounterRates {
+    let seconds = elapsed.as_secs_f64();
+    #[allow(clippy::cast_precision_loss)]
+    let rate = |before: Option<u64>, after: Option<u64>| {
+        let delta = after?.checked_sub(before?)?;
+        (seconds > 0.0).then(|| delta as f64 / seconds)
+    };
+
+    OntCounterRates {
+        upstream_bps: rate(earlier.upstream_bytes, later.upstream_bytes).map(|r| r * 8.0),
+        downstream_bps: rate(earlier.downstream_bytes, later.downstream_bytes).map(|r| r * 8.0),
+        upstream_frames_per_second: rate(earlier.upstream_frames, later.upstream_frames),
+        downstream_frames_per_second: rate(earlier.downstream_frames, later.downstream_frames),
+        upstream_bip_errors_per_second: rate(
+            earlier.upstream_bip_errors,
+            later.upstream_bip_errors,
+        ),
+        downstream_bip_errors_per_second: rate(
+            earlier.downstream_bip_errors,
+            later.downstream_bip_errors,
+        ),
+        upstream_fec_corrected_per_second: rate(
+            earlier.upstream_fec_corrected_codewords,
+            later.upstream_fec_corrected_codewords,
+        ),
+        downstream_fec_corrected_per_second: rate(
+            earlier.downstream_fec_corrected_codewords,
+            later.downstream_fec_corrected_codewords,
+        ),
+        gem_hec_errors_per_second: rate(earlier.gem_hec_errors, later.gem_hec_errors),
+    }
+}
+
+/// Parses both `display statistics ont` and `display statistics ont-line-quality`.
+#[must_use]
+pub fn parse_ont_counters(output: &str) -> OntCounters {
+    let mut counters = OntCounters::default();
+
+    for (key, value) in key_values(output) {
+        let value = value.parse().ok();
+
+        let counter = match key {
+            "Upstream frames received" => &mut counters.upstream_frames,
+            "Upstream bytes received" => &mut counters.upstream_bytes,
+            "Upstream dropped frames" => &mut counters.upstream_dropped_frames,
+            "Downstream frames sent" => &mut counters.downstream_frames,
+            "Downstream bytes sent" => &mut counters.downstream_bytes,
+            "Downstream dropped frames" => &mut counters.downstream_dropped_frames,
+            "Upstream frame BIP error count" => &mut counters.upstream_bip_errors,
+            "Downstream frame BIP error count" => &mut counters.downstream_bip_errors,
+            "Upstream FEC corrected bytes" => &mut counters.upstream_fec_corrected_bytes,
+            "Upstream FEC corrected codewords" => &mut counters.upstream_fec_corrected_codewords,
+            "Upstream FEC uncorrectable codewords" => {
+                &mut counters.upstream_fec_uncorrectable_codewords
+            }
+            "Downstream FEC corrected bytes" => &mut counters.downstream_fec_corrected_bytes,
+            "Downstream FEC corrected codewords" => {
+                &mut counters.downstream_fec_corrected_codewords
+            }
+            "Downstream FEC uncorrectable codewords" => {
+                &mut counters.downstream_fec_uncorrectable_codewords
+            }
+            "GEM HEC error count" => &mut counters.gem_hec_errors,
+            _ => continue,
+        };
+        *counter = value;
+    }
+
+    counters
+}
+
+#[must_use]
+pub fn parse_ont_traffic(output: &str) -> Vec<OntTraffic> {
+    let mut traffic = Vec::new();
+
+    for line in output.lines() {
+        // Example: "0     1524          30218"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 3 {
+            continue;
+        }
+        if let (Ok(ont_id), Ok(upstream_kbps), Ok(downstream_kbps)) =
+            (parts[0].parse(), parts[1].parse(), parts[2].parse())
+        {
+            traffic.push(OntTraffic {
+                ont_id,
+                upstream_kbps,
+                downstream_kbps,
+            });
+        }
+    }
+
+    traffic
+}
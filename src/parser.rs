@@ -1,8 +1,13 @@
 use crate::{
-    models::{BoardSlot, OntAutofindEntry, OntInfo, OpticalInfo, ServicePort},
+    models::{
+        BoardDetail, BoardPort, BoardSlot, OntAutofindEntry, OntDownCause, OntEthPortState,
+        OntInfo, OntInfoList, OntInfoListEntry, OntIpConfig, OntOpticalLevels, OntPortAttribute,
+        OntRegisterRecord, OntVersion, OntWanInfo, OpticalInfo, ServicePort, ServicePortTable,
+    },
     Fsp,
 };
 use regex::Regex;
+use std::fmt::Write;
 
 #[must_use]
 pub fn parse_ont_autofind(output: &str) -> Vec<OntAutofindEntry> {
@@ -115,7 +120,7 @@ pub fn parse_ont_info(output: &str) -> Option<OntInfo> {
         sn_readable: String::new(),
         management_mode: String::new(),
         description: String::new(),
-        last_down_cause: String::new(),
+        last_down_cause: OntDownCause::Unknown,
         last_up_time: String::new(),
         last_down_time: String::new(),
         online_duration: String::new(),
@@ -206,7 +211,7 @@ pub fn parse_ont_info(output: &str) -> Option<OntInfo> {
                     found_data = true;
                 }
                 "Last down cause" => {
-                    info.last_down_cause = value.to_string();
+                    info.last_down_cause = OntDownCause::from(value);
                     found_data = true;
                 }
                 "Last up time" => {
@@ -361,30 +366,311 @@ pub fn parse_optical_info(output: &str) -> Option<OpticalInfo> {
     }
 }
 
+/// Parses one or more ONT version blocks, one per `F/S/P` line.
+///
+/// Blocks without a valid `ONT-ID` are skipped.
+#[must_use]
+pub fn parse_ont_versions(output: &str) -> Vec<OntVersion> {
+    let mut records: Vec<(OntVersion, Option<u32>)> = Vec::new();
+
+    for (key, value) in key_values(output) {
+        if key == "F/S/P" {
+            if let Some(fsp) = Fsp::parse(value) {
+                records.push((
+                    OntVersion {
+                        fsp,
+                        ..OntVersion::default()
+                    },
+                    None,
+                ));
+            }
+            continue;
+        }
+
+        let Some((version, ont_id)) = records.last_mut() else {
+            continue;
+        };
+        match key {
+            "ONT-ID" => *ont_id = value.parse().ok(),
+            "Vendor-ID" => version.vendor_id = value.to_string(),
+            "ONT Version" => version.ont_version = value.to_string(),
+            "Product Description" => version.product_description = value.to_string(),
+            "Equipment-ID" => version.equipment_id = value.to_string(),
+            "Main Software Version" => version.main_software_version = value.to_string(),
+            "Standby Software Version" => version.standby_software_version = optional_value(value),
+            "OUI Version" => version.oui_version = optional_value(value),
+            _ => {}
+        }
+    }
+
+    records
+        .into_iter()
+        .filter_map(|(version, ont_id)| {
+            Some(OntVersion {
+                ont_id: ont_id?,
+                ..version
+            })
+        })
+        .collect()
+}
+
+/// Parses `display ont wan-info`, one entry per WAN `Index`.
+#[must_use]
+pub fn parse_ont_wan_info(output: &str) -> Vec<OntWanInfo> {
+    let mut wans: Vec<OntWanInfo> = Vec::new();
+
+    for (key, value) in key_values(output) {
+        if key == "Index" {
+            if let Ok(index) = value.parse() {
+                wans.push(OntWanInfo {
+                    index,
+                    ..OntWanInfo::default()
+                });
+            }
+            continue;
+        }
+
+        let Some(wan) = wans.last_mut() else {
+            continue;
+        };
+
+        match key {
+            "Name" => wan.name = value.to_string(),
+            "Service type" => wan.service_type = value.to_string(),
+            "Connection type" => wan.connection_type = value.to_string(),
+            "IPv4 Connection status" => wan.ipv4_status = value.to_string(),
+            "IPv4 access type" => wan.ipv4_access_type = value.to_string(),
+            "IPv4 address" => wan.ipv4_address = optional_value(value),
+            "Subnet mask" => wan.subnet_mask = optional_value(value),
+            "Default gateway" => wan.gateway = optional_value(value),
+            "Primary DNS" => wan.primary_dns = optional_value(value),
+            "Secondary DNS" => wan.secondary_dns = optional_value(value),
+            "Manage VLAN" => wan.vlan = value.parse().ok(),
+            "MAC address" => wan.mac_address = optional_value(value),
+            "PPPoE user name" => wan.pppoe_username = optional_value(value),
+            "IPv6 Connection status" => wan.ipv6_status = optional_value(value),
+            "IPv6 address" => wan.ipv6_address = optional_value(value),
+            "Prefix" => wan.ipv6_prefix = optional_value(value),
+            _ => {}
+        }
+    }
+
+    wans
+}
+
+/// Parses `display ont ipconfig`, one entry per `IP index`.
+#[must_use]
+pub fn parse_ont_ipconfig(output: &str) -> Vec<OntIpConfig> {
+    let mut configs: Vec<OntIpConfig> = Vec::new();
+
+    for (key, value) in key_values(output) {
+        if key.eq_ignore_ascii_case("IP index") {
+            if let Ok(index) = value.parse() {
+                configs.push(OntIpConfig {
+                    index,
+                    ..OntIpConfig::default()
+                });
+            }
+            continue;
+        }
+
+        let Some(config) = configs.last_mut() else {
+            continue;
+        };
+
+        match key {
+            "Config type" => config.config_type = value.to_string(),
+            "IP address" => config.ip_address = optional_value(value),
+            "Subnet mask" => config.subnet_mask = optional_value(value),
+            "Default gateway" => config.gateway = optional_value(value),
+            "Primary DNS" => config.primary_dns = optional_value(value),
+            "Slave DNS" | "Secondary DNS" => config.secondary_dns = optional_value(value),
+            "MAC address" => config.mac_address = optional_value(value),
+            "Manage VLAN" => config.vlan = value.parse().ok(),
+            "Manage priority" => config.priority = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    configs
+}
+
+/// Iterates over the trimmed `key : value` lines of `output`, or `key   value`
+/// lines when the OLT omits the colon.
+pub(crate) fn key_values(output: &str) -> impl Iterator<Item = (&str, &str)> {
+    output.lines().filter_map(|line| {
+        let line = line.trim();
+        let (key, value) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => line.split_once("  ")?,
+        };
+        Some((key.trim(), value.trim()))
+    })
+}
+
+/// Maps the `-` placeholder to `None`.
+fn optional_value(value: &str) -> Option<String> {
+    (value != "-" && !value.is_empty()).then(|| value.to_string())
+}
+
+#[must_use]
+pub fn parse_ont_optical_levels(output: &str) -> Vec<OntOpticalLevels> {
+    let mut levels = Vec::new();
+
+    for line in output.lines() {
+        // Example: "0      -15.93    2.34      -23.67      37           3.260    9"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 7 {
+            continue;
+        }
+        let Ok(ont_id) = parts[0].parse() else {
+            continue;
+        };
+
+        levels.push(OntOpticalLevels {
+            ont_id,
+            rx_power: parts[1].parse().ok(),
+            tx_power: parts[2].parse().ok(),
+            olt_rx_power: parts[3].parse().ok(),
+            temperature: parts[4].parse().ok(),
+            voltage: parts[5].parse().ok(),
+            bias_current: parts[6].parse().ok(),
+        });
+    }
+
+    levels
+}
+
 #[must_use]
 pub fn parse_service_ports(output: &str) -> Vec<ServicePort> {
-    let mut ports = Vec::new();
+    parse_service_port_table(output).ports
+}
+
+#[must_use]
+pub fn parse_service_port_table(output: &str) -> ServicePortTable {
+    let mut table = ServicePortTable::default();
 
     if output.contains("No service virtual port can be operated") {
-        return ports;
+        return table;
     }
 
     // Regex to match service port lines
     // Example real output: "     68 1063 common   gpon 0/9 /2  0    20    vlan  20         10   10   up"
-    // Captures: INDEX (68), VLAN ID (1063)
-    let Ok(re) = Regex::new(r"(?m)^\s+(\d+)\s+(\d+)\s+\w+\s+gpon") else {
-        return ports;
+    // Captures: INDEX, VLAN ID, VLAN ATTR, PORT TYPE, F/S/P, VPI, VCI, FLOW TYPE,
+    // FLOW PARA, RX, TX, STATE
+    let Ok(re) = Regex::new(
+        r"(?m)^\s*(\d+)\s+(\d+)\s+(\S+)\s+(\S+)\s+(\d+\s*/\s*\d+\s*/\s*\d+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s*$",
+    ) else {
+        return table;
     };
 
     for cap in re.captures_iter(output) {
-        if let (Some(index), Some(vlan)) = (cap.get(1), cap.get(2)) {
-            if let (Ok(index), Ok(vlan)) = (index.as_str().parse(), vlan.as_str().parse()) {
-                ports.push(ServicePort { index, vlan });
+        let text = |i: usize| cap.get(i).map_or("", |m| m.as_str());
+        let number = |i: usize| text(i).parse::<u32>().ok();
+
+        let (Some(index), Some(vlan), Some(fsp)) = (number(1), number(2), Fsp::parse(text(5)))
+        else {
+            continue;
+        };
+
+        table.ports.push(ServicePort {
+            index,
+            vlan,
+            vlan_attr: text(3).to_string(),
+            port_type: text(4).to_string(),
+            fsp,
+            ont_id: number(6),
+            gemport: number(7),
+            flow_type: text(8).to_string(),
+            flow_para: text(9).to_string(),
+            inbound_traffic_table: number(10),
+            outbound_traffic_table: number(11),
+            state: text(12).to_string(),
+        });
+    }
+
+    // Example: " Total : 1  (Up/Down :    1/0)"
+    if let Ok(footer) = Regex::new(r"Total\s*:\s*(\d+)\s*\(Up/Down\s*:\s*(\d+)\s*/\s*(\d+)\)") {
+        if let Some(cap) = footer.captures(output) {
+            let number = |i: usize| cap.get(i).and_then(|m| m.as_str().parse().ok());
+            table.total = number(1).unwrap_or(0);
+            table.up = number(2).unwrap_or(0);
+            table.down = number(3).unwrap_or(0);
+        }
+    }
+
+    table
+}
+
+#[must_use]
+pub fn parse_board_detail(output: &str) -> Option<BoardDetail> {
+    let mut detail = BoardDetail::default();
+    let mut found = false;
+
+    // Example: "In port 0/ 5/0 , the total of ONTs are: 62, online: 58"
+    let ont_count_re = Regex::new(
+        r"In port\s+(\d+\s*/\s*\d+\s*/\s*\d+)\s*,\s*the total of ONTs are:\s*(\d+),\s*online:\s*(\d+)",
+    )
+    .ok()?;
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "Board Name" => {
+                    detail.board_name = value.to_string();
+                    found = true;
+                    continue;
+                }
+                "Board Status" => {
+                    detail.status = value.to_string();
+                    continue;
+                }
+                "Online state" => {
+                    detail.online_state = (value != "-").then(|| value.to_string());
+                    continue;
+                }
+                _ => {}
             }
         }
+
+        if let Some(cap) = ont_count_re.captures(line) {
+            let port = cap
+                .get(1)
+                .and_then(|m| Fsp::parse(m.as_str()))
+                .map(|fsp| fsp.port);
+            let number = |i: usize| cap.get(i).and_then(|m| m.as_str().parse().ok());
+            if let Some(board_port) = detail.ports.iter_mut().find(|p| Some(p.port) == port) {
+                board_port.ont_total = number(2);
+                board_port.ont_online = number(3);
+            }
+            continue;
+        }
+
+        // Example: "0  GPON       Enable       0                 20                Online"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 6 {
+            continue;
+        }
+        let Ok(port) = parts[0].parse() else {
+            continue;
+        };
+
+        detail.ports.push(BoardPort {
+            port,
+            port_type: parts[1].to_string(),
+            port_status: parts[2].to_string(),
+            min_distance_km: parts[3].parse().ok(),
+            max_distance_km: parts[4].parse().ok(),
+            optical_module_status: parts[5].to_string(),
+            ont_total: None,
+            ont_online: None,
+        });
     }
 
-    ports
+    found.then_some(detail)
 }
 
 #[must_use]
@@ -473,6 +759,197 @@ pub fn parse_display_board(output: &str) -> Vec<BoardSlot> {
     slots
 }
 
+#[must_use]
+pub fn parse_ont_port_attributes(output: &str) -> Vec<OntPortAttribute> {
+    let mut attributes = Vec::new();
+
+    for line in output.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 9 {
+            continue;
+        }
+
+        let (Ok(ont_id), Ok(port_id)) = (parts[0].parse(), parts[1].parse()) else {
+            continue;
+        };
+
+        attributes.push(OntPortAttribute {
+            ont_id,
+            port_id,
+            port_type: parts[2].to_string(),
+            speed: parts[3].to_string(),
+            duplex: parts[4].to_string(),
+            native_vlan: parts[5].parse().ok(),
+            priority: parts[6].parse().ok(),
+            flow_control: parts[7].to_string(),
+            active_state: parts[8].to_string(),
+        });
+    }
+
+    attributes
+}
+
+/// Parses `display ont register-info`, one record per `Index` block.
+#[must_use]
+pub fn parse_ont_register_info(output: &str) -> Vec<OntRegisterRecord> {
+    let mut records: Vec<OntRegisterRecord> = Vec::new();
+
+    for (key, value) in key_values(output) {
+        if key == "Index" {
+            if let Ok(index) = value.parse() {
+                records.push(OntRegisterRecord {
+                    index,
+                    ..OntRegisterRecord::default()
+                });
+            }
+            continue;
+        }
+
+        let Some(record) = records.last_mut() else {
+            continue;
+        };
+
+        match key {
+            "Type" => record.auth_type = value.to_string(),
+            "SN" => {
+                record.sn = value.split_whitespace().next().unwrap_or("").to_string();
+            }
+            "UpTime" | "Uptime" => record.up_time = optional_value(value),
+            "DownTime" | "Downtime" => record.down_time = optional_value(value),
+            "DownCause" => record.down_cause = OntDownCause::from(value),
+            _ => {}
+        }
+    }
+
+    records
+}
+
+#[must_use]
+pub fn parse_ont_eth_port_states(output: &str) -> Vec<OntEthPortState> {
+    let mut states = Vec::new();
+
+    for line in output.lines() {
+        // Example: "0     1    GE        1000     full       up         disable"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 7 {
+            continue;
+        }
+
+        let (Ok(ont_id), Ok(port_id)) = (parts[0].parse(), parts[1].parse()) else {
+            continue;
+        };
+
+        states.push(OntEthPortState {
+            ont_id,
+            port_id,
+            port_type: parts[2].to_string(),
+            speed_mbps: parts[3].parse().ok(),
+            duplex: optional_value(parts[4]),
+            link_state: parts[5].to_string(),
+            ring_status: parts[6].to_string(),
+        });
+    }
+
+    states
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListSection {
+    None,
+    States,
+    Descriptions,
+}
+
+#[must_use]
+pub fn parse_ont_info_list(output: &str) -> OntInfoList {
+    let (Ok(port_re), Ok(state_re), Ok(description_re)) = (
+        Regex::new(
+            r"^In port (\d+\s*/\s*\d+\s*/\s*\d+)\s*, the total of ONTs are: (\d+), online: (\d+)",
+        ),
+        Regex::new(
+            r"^(\d+\s*/\s*\d+\s*/\s*\d+)\s+(\d+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)$",
+        ),
+        Regex::new(r"^(\d+\s*/\s*\d+\s*/\s*\d+)\s+(\d+)\s*(.*)$"),
+    ) else {
+        return OntInfoList::default();
+    };
+
+    let mut list = OntInfoList::default();
+    let mut section = ListSection::None;
+
+    let cleaned = output.replace('\r', "\n");
+    for line in cleaned.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("---") {
+            continue;
+        }
+
+        if line.starts_with("F/S/P") {
+            section = if line.contains("Description") {
+                ListSection::Descriptions
+            } else {
+                ListSection::States
+            };
+            continue;
+        }
+
+        if let Some(caps) = port_re.captures(line) {
+            let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse().ok());
+            list.fsp = caps
+                .get(1)
+                .and_then(|m| Fsp::parse(m.as_str()))
+                .unwrap_or_default();
+            list.total_onts = number(2).unwrap_or(0);
+            list.online_onts = number(3).unwrap_or(0);
+            section = ListSection::None;
+            continue;
+        }
+
+        match section {
+            ListSection::States => {
+                let Some(caps) = state_re.captures(line) else {
+                    continue;
+                };
+                let text = |i: usize| caps.get(i).map_or("", |m| m.as_str());
+                let (Some(fsp), Ok(id)) = (Fsp::parse(text(1)), text(2).parse()) else {
+                    continue;
+                };
+
+                list.onts.push(OntInfoListEntry {
+                    fsp,
+                    id,
+                    sn: text(3).to_string(),
+                    control_flag: text(4).to_string(),
+                    run_state: text(5).to_string(),
+                    config_state: text(6).to_string(),
+                    match_state: text(7).to_string(),
+                    protect_side: text(8).to_string(),
+                    description: String::new(),
+                });
+            }
+            ListSection::Descriptions => {
+                let Some(caps) = description_re.captures(line) else {
+                    continue;
+                };
+                let fsp = caps.get(1).and_then(|m| Fsp::parse(m.as_str()));
+                let id = caps.get(2).and_then(|m| m.as_str().parse::<u32>().ok());
+                let description = caps.get(3).map_or("", |m| m.as_str().trim());
+
+                if let Some(ont) = list
+                    .onts
+                    .iter_mut()
+                    .find(|o| Some(o.fsp) == fsp && Some(o.id) == id)
+                {
+                    ont.description = description.to_string();
+                }
+            }
+            ListSection::None => {}
+        }
+    }
+
+    list
+}
+
 #[must_use]
 pub fn extract_ont_id(output: &str) -> Option<u32> {
     let re = Regex::new(r"ONTID\s*:(\d+)").ok()?;
@@ -481,6 +958,80 @@ pub fn extract_ont_id(output: &str) -> Option<u32> {
         .and_then(|m| m.as_str().parse().ok())
 }
 
+#[must_use]
+pub fn extract_next_free_service_port_index(output: &str) -> Option<u32> {
+    let re = Regex::new(r"free service virtual port ID\s*:\s*(\d+)").ok()?;
+    re.captures(output)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// Normalizes an ONT serial number to the raw hexadecimal form used by the OLT.
+///
+/// Accepts the raw form (`48575443CB8FBDB4`), the readable form (`HWTC-CB8FBDB4`)
+/// and the combined form printed by the OLT (`48575443CB8FBDB4 (HWTC-CB8FBDB4)`).
+#[must_use]
+pub fn normalize_serial_number(sn: &str) -> String {
+    let sn = sn.split_whitespace().next().unwrap_or(sn);
+
+    if let Some((vendor, serial)) = sn.split_once('-') {
+        if vendor.len() == 4 && vendor.is_ascii() {
+            let mut raw = String::with_capacity(sn.len() + 3);
+            for byte in vendor.bytes() {
+                let _ = write!(raw, "{byte:02X}");
+            }
+            raw.push_str(&serial.to_ascii_uppercase());
+            return raw;
+        }
+    }
+
+    sn.to_ascii_uppercase()
+}
+
+/// Pager prompt printed between pages of long output
+pub const MORE_PROMPT: &str = "---- More ( Press 'Q' to break ) ----";
+
+/// Removes the pager prompt from a line and replays the cursor movements the
+/// OLT uses to erase it.
+#[must_use]
+pub fn strip_pager(line: &str) -> String {
+    let line = line.replace(MORE_PROMPT, "");
+    let mut cleaned: Vec<char> = Vec::with_capacity(line.len());
+    let mut cursor = 0;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            if cursor < cleaned.len() {
+                cleaned[cursor] = c;
+            } else {
+                cleaned.push(c);
+            }
+            cursor += 1;
+            continue;
+        }
+
+        // CSI sequence such as "ESC[37D" (cursor left by 37 columns)
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+        let mut count = String::new();
+        for c in chars.by_ref() {
+            if c.is_ascii_digit() {
+                count.push(c);
+                continue;
+            }
+            if c == 'D' {
+                cursor = cursor.saturating_sub(count.parse().unwrap_or(1));
+            }
+            break;
+        }
+    }
+
+    cleaned.into_iter().collect()
+}
+
 pub fn check_for_failure(output: &str) -> crate::error::Result<()> {
     for line in output.lines() {
         let line = line.trim();
@@ -497,6 +1048,19 @@ pub fn check_for_failure(output: &str) -> crate::error::Result<()> {
     Ok(())
 }
 
+/// Wraps `value` in double quotes for use as a CLI argument.
+///
+/// The CLI has no escape for `"`, so empty values and values containing a quote
+/// or a control character are rejected.
+pub fn quote_argument(value: &str) -> crate::error::Result<String> {
+    if value.is_empty() || value.contains('"') || value.chars().any(char::is_control) {
+        return Err(crate::error::Error::CommandFailed(format!(
+            "Invalid argument {value:?}"
+        )));
+    }
+    Ok(format!("\"{value}\""))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -659,20 +1223,28 @@ mod tests {
         let output = "     68 1063 common   gpon 0/9 /2  0    20    vlan  20         10   10   up\n     69 1064 common   gpon 0/9 /3  0    20    vlan  20         10   10   up\n";
         let ports = parse_service_ports(output);
         assert_eq!(ports.len(), 2);
+        assert_eq!((ports[0].index, ports[0].vlan), (68, 1063));
+        assert_eq!((ports[1].index, ports[1].vlan), (69, 1064));
         assert_eq!(
-            ports[0],
-            ServicePort {
-                index: 68,
-                vlan: 1063
-            }
-        );
-        assert_eq!(
-            ports[1],
-            ServicePort {
-                index: 69,
-                vlan: 1064
+            ports[1].fsp,
+            Fsp {
+                frame: 0,
+                slot: 9,
+                port: 3
             }
         );
+        assert_eq!(ports[1].gemport, Some(20));
+        assert_eq!(ports[1].state, "up");
+    }
+
+    #[test]
+    fn parse_service_port_table_reads_footer() {
+        let output = "     68 1063 common   gpon 0/9 /2  0    20    vlan  20         10   10   up
+   Total : 1  (Up/Down :    1/0)
+";
+        let table = parse_service_port_table(output);
+        assert_eq!(table.ports.len(), 1);
+        assert_eq!((table.total, table.up, table.down), (1, 1, 0));
     }
 
     #[test]
@@ -696,6 +1268,26 @@ mod tests {
         assert!(id.is_none());
     }
 
+    #[test]
+    fn extract_next_free_service_port_index_parses_value() {
+        let output = "  Next valid free service virtual port ID: 2349\n";
+        assert_eq!(extract_next_free_service_port_index(output), Some(2349));
+        assert_eq!(extract_next_free_service_port_index("Failure"), None);
+    }
+
+    #[test]
+    fn normalize_serial_number_forms() {
+        assert_eq!(
+            normalize_serial_number("48575443cb8fbdb4"),
+            "48575443CB8FBDB4"
+        );
+        assert_eq!(normalize_serial_number("HWTC-CB8FBDB4"), "48575443CB8FBDB4");
+        assert_eq!(
+            normalize_serial_number("48575443CB8FBDB4 (HWTC-CB8FBDB4)"),
+            "48575443CB8FBDB4"
+        );
+    }
+
     #[test]
     fn check_for_failure_ok() {
         let output = "OK\n";
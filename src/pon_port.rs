@@ -0,0 +1,160 @@
+use crate::models::Fsp;
+use crate::parser::key_values;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// PON port state from `display port state <port>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq)]
+pub struct PonPortState {
+    pub fsp: Fsp,
+    /// `Online` when an optical module is inserted
+    pub optical_module_status: String,
+    /// Operational state (e.g. `Online`, `Offline`)
+    pub port_state: String,
+    /// Laser state (e.g. `Normal`, `Shutdown`)
+    pub laser_state: String,
+    pub available_bandwidth_kbps: Option<u32>,
+    /// Module temperature in Celsius
+    pub temperature: Option<i32>,
+    /// Laser bias current in mA
+    pub bias_current: Option<f32>,
+    /// Supply voltage in V
+    pub supply_voltage: Option<f32>,
+    /// OLT Tx optical power in dBm
+    pub tx_power: Option<f32>,
+    pub illegal_rogue_ont: String,
+    pub max_distance_km: Option<u32>,
+    pub wavelength_nm: Option<u32>,
+}
+
+impl PonPortState {
+    #[must_use]
+    pub fn is_online(&self) -> bool {
+        self.port_state.eq_ignore_ascii_case("online")
+    }
+}
+
+/// PON port configuration from `display port info <port>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct PonPortInfo {
+    pub fsp: Fsp,
+    pub port_type: String,
+    /// Administrative state (e.g. `Activate`, `Deactivate`)
+    pub admin_state: String,
+    /// Operational state (e.g. `Up`, `Down`)
+    pub operational_state: String,
+    pub min_distance_km: Option<u32>,
+    pub max_distance_km: Option<u32>,
+    pub ont_autofind: String,
+    pub ont_total: Option<u32>,
+    pub ont_online: Option<u32>,
+}
+
+/// OLT-side optical module from `display port ddm-info <port>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq)]
+pub struct PonOpticalModule {
+    pub fsp: Fsp,
+    pub module_type: String,
+    pub module_sub_type: String,
+    pub vendor_name: String,
+    pub vendor_pn: String,
+    pub vendor_sn: String,
+    pub wavelength_nm: Option<u32>,
+    /// Module temperature in Celsius
+    pub temperature: Option<i32>,
+    /// Supply voltage in V
+    pub supply_voltage: Option<f32>,
+    /// Laser bias current in mA
+    pub bias_current: Option<f32>,
+    /// OLT Tx optical power in dBm
+    pub tx_power: Option<f32>,
+}
+
+#[must_use]
+pub fn parse_pon_port_state(output: &str) -> Option<PonPortState> {
+    let mut state = PonPortState::default();
+    let mut found = false;
+
+    for (key, value) in key_values(output) {
+        match key {
+            "F/S/P" => {
+                if let Some(fsp) = Fsp::parse(value) {
+                    state.fsp = fsp;
+                    found = true;
+                }
+            }
+            "Optical Module status" => state.optical_module_status = value.to_string(),
+            "Port state" => state.port_state = value.to_string(),
+            "Laser state" => state.laser_state = value.to_string(),
+            "Available bandwidth(Kbps)" => state.available_bandwidth_kbps = value.parse().ok(),
+            "Temperature(C)" => state.temperature = value.parse().ok(),
+            "TX Bias current(mA)" => state.bias_current = value.parse().ok(),
+            "Supply Voltage(V)" => state.supply_voltage = value.parse().ok(),
+            "TX power(dBm)" => state.tx_power = value.parse().ok(),
+            "Illegal rogue ONT" => state.illegal_rogue_ont = value.to_string(),
+            "Max Distance(Km)" => state.max_distance_km = value.parse().ok(),
+            "Wave length(nm)" => state.wavelength_nm = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    found.then_some(state)
+}
+
+#[must_use]
+pub fn parse_pon_port_info(output: &str) -> Option<PonPortInfo> {
+    let mut info = PonPortInfo::default();
+    let mut found = false;
+
+    for (key, value) in key_values(output) {
+        match key {
+            "F/S/P" => {
+                if let Some(fsp) = Fsp::parse(value) {
+                    info.fsp = fsp;
+                    found = true;
+                }
+            }
+            "Port type" => info.port_type = value.to_string(),
+            "Admin state" => info.admin_state = value.to_string(),
+            "Operational state" => info.operational_state = value.to_string(),
+            "Min distance(km)" => info.min_distance_km = value.parse().ok(),
+            "Max distance(km)" => info.max_distance_km = value.parse().ok(),
+            "ONT auto-find switch" => info.ont_autofind = value.to_string(),
+            "Number of ONTs" => info.ont_total = value.parse().ok(),
+            "Number of online ONTs" => info.ont_online = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    found.then_some(info)
+}
+
+#[must_use]
+pub fn parse_pon_optical_module(output: &str) -> Option<PonOpticalModule> {
+    let mut module = PonOpticalModule::default();
+    let mut found = false;
+
+    for (key, value) in key_values(output) {
+        match key {
+            "Port" | "F/S/P" => {
+                if let Some(fsp) = Fsp::parse(value) {
+                    module.fsp = fsp;
+                    found = true;
+                }
+            }
+            "Module type" => module.module_type = value.to_string(),
+            "Module sub-type" => module.module_sub_type = value.to_string(),
+            "Vendor name" => module.vendor_name = value.to_string(),
+            "Vendor PN" => module.vendor_pn = value.to_string(),
+            "Vendor SN" => module.vendor_sn = value.to_string(),
+            "Wave length(nm)" => module.wavelength_nm = value.parse().ok(),
+            "Temperature(C)" => module.temperature = value.parse().ok(),
+            "Supply Voltage(V)" => module.supply_voltage = value.parse().ok(),
+            "TX Bias current(mA)" => module.bias_current = value.parse().ok(),
+            "TX power(dBm)" => module.tx_power = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    found.then_some(module)
+}
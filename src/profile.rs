@@ -0,0 +1,397 @@
+//! GPON line, service and DBA profiles.
+//!
+//! `OntInfo` only carries profile IDs and names; these models describe what the
+//! profiles contain, e.g. which GEM port a line profile maps a VLAN to.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::Result;
+use crate::models::NativeVlan;
+use crate::parser::quote_argument;
+
+/// Row of `display ont-lineprofile gpon all` or `display ont-srvprofile gpon all`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ProfileSummary {
+    pub id: u32,
+    pub name: String,
+    /// Number of ONTs bound to the profile
+    pub binding_times: u32,
+}
+
+/// Line profile from `display ont-lineprofile gpon profile-id <id>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct LineProfile {
+    pub id: u32,
+    pub name: String,
+    pub access_type: String,
+    pub fec_upstream: String,
+    pub omcc_encrypt: String,
+    pub qos_mode: String,
+    /// GEM mapping mode (e.g. `VLAN`, `Priority`)
+    pub mapping_mode: String,
+    pub tr069_management: String,
+    pub tconts: Vec<Tcont>,
+    pub gem_ports: Vec<GemPort>,
+    pub binding_times: Option<u32>,
+}
+
+impl LineProfile {
+    /// Returns the GEM index mapping `vlan`, if any.
+    #[must_use]
+    pub fn gem_for_vlan(&self, vlan: u32) -> Option<u32> {
+        self.gem_ports
+            .iter()
+            .find(|g| g.mappings.iter().any(|m| m.vlan == Some(vlan)))
+            .map(|g| g.index)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct Tcont {
+    pub id: u32,
+    pub dba_profile_id: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct GemPort {
+    pub index: u32,
+    /// T-CONT the GEM port is bound to
+    pub tcont_id: u32,
+    /// Service type (e.g. `ETH`)
+    pub service_type: String,
+    pub encrypt: String,
+    pub mappings: Vec<GemMapping>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct GemMapping {
+    pub index: u32,
+    pub vlan: Option<u32>,
+    pub priority: Option<u32>,
+    pub port_type: Option<String>,
+    pub port_id: Option<u32>,
+}
+
+/// Service profile from `display ont-srvprofile gpon profile-id <id>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ServiceProfile {
+    pub id: u32,
+    pub name: String,
+    pub access_type: String,
+    pub ports: Vec<ServiceProfilePort>,
+    pub native_vlans: Vec<NativeVlan>,
+    pub binding_times: Option<u32>,
+}
+
+/// Number of ONT ports of one type declared in a service profile
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ServiceProfilePort {
+    /// Port type as printed by the OLT (e.g. `ETH`, `POTS`)
+    pub port_type: String,
+    /// `true` when the port count adapts to the ONT
+    pub adaptive: bool,
+    /// Fixed port count, `None` when adaptive
+    pub count: Option<u32>,
+    /// Upper bound for adaptive port counts
+    pub max_adaptive: Option<u32>,
+}
+
+/// Row of `display dba-profile all`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct DbaProfile {
+    pub id: u32,
+    /// DBA type 1-5 (fix, assure, assure+max, max, fix+assure+max)
+    pub dba_type: u32,
+    pub bandwidth_compensate: bool,
+    pub fix_kbps: u32,
+    pub assure_kbps: u32,
+    pub max_kbps: u32,
+    pub binding_times: u32,
+}
+
+/// Bandwidth of a DBA profile, one variant per DBA type (all values in kbit/s)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DbaBandwidth {
+    /// Type 1
+    Fix { fix: u32 },
+    /// Type 2
+    Assure { assure: u32 },
+    /// Type 3
+    AssureMax { assure: u32, max: u32 },
+    /// Type 4
+    Max { max: u32 },
+    /// Type 5
+    FixAssureMax { fix: u32, assure: u32, max: u32 },
+}
+
+impl DbaBandwidth {
+    /// Returns the `typeN ...` arguments of `dba-profile add`/`modify`.
+    #[must_use]
+    pub fn arguments(&self) -> String {
+        match self {
+            Self::Fix { fix } => format!("type1 fix {fix}"),
+            Self::Assure { assure } => format!("type2 assure {assure}"),
+            Self::AssureMax { assure, max } => format!("type3 assure {assure} max {max}"),
+            Self::Max { max } => format!("type4 max {max}"),
+            Self::FixAssureMax { fix, assure, max } => {
+                format!("type5 fix {fix} assure {assure} max {max}")
+            }
+        }
+    }
+}
+
+/// Returns the `dba-profile add` command creating profile `id` as `name`.
+pub fn dba_profile_add_command(id: u32, name: &str, bandwidth: DbaBandwidth) -> Result<String> {
+    Ok(format!(
+        "dba-profile add profile-id {id} profile-name {} {}",
+        quote_argument(name)?,
+        bandwidth.arguments()
+    ))
+}
+
+/// Port count declared with `ont-port` in a service profile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PortCount {
+    /// The count follows what the ONT reports
+    Adaptive,
+    Fixed(u32),
+}
+
+impl std::fmt::Display for PortCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Adaptive => f.write_str("adaptive"),
+            Self::Fixed(count) => write!(f, "{count}"),
+        }
+    }
+}
+
+/// Parses the profile list of `display ont-lineprofile gpon all` or
+/// `display ont-srvprofile gpon all`.
+#[must_use]
+pub fn parse_profile_summaries(output: &str) -> Vec<ProfileSummary> {
+    let mut profiles = Vec::new();
+
+    for line in output.lines() {
+        // Example: "60          FTTH_1063                                   412"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 3 {
+            continue;
+        }
+        if let (Ok(id), Ok(binding_times)) = (parts[0].parse(), parts[2].parse()) {
+            profiles.push(ProfileSummary {
+                id,
+                name: parts[1].to_string(),
+                binding_times,
+            });
+        }
+    }
+
+    profiles
+}
+
+#[must_use]
+pub fn parse_line_profile(output: &str) -> Option<LineProfile> {
+    let tcont_re = Regex::new(r"<T-CONT\s+(\d+)>\s+DBA Profile-ID:\s*(\d+)").ok()?;
+    let gem_re = Regex::new(r"<Gem Index\s+(\d+)>").ok()?;
+
+    let mut profile = LineProfile::default();
+    let mut found = false;
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if let Some(cap) = tcont_re.captures(line) {
+            let number = |i: usize| cap.get(i).and_then(|m| m.as_str().parse().ok());
+            if let (Some(id), Some(dba_profile_id)) = (number(1), number(2)) {
+                profile.tconts.push(Tcont { id, dba_profile_id });
+            }
+            continue;
+        }
+
+        if let Some(cap) = gem_re.captures(line) {
+            if let Some(index) = cap.get(1).and_then(|m| m.as_str().parse().ok()) {
+                profile.gem_ports.push(GemPort {
+                    index,
+                    tcont_id: profile.tconts.last().map_or(0, |t| t.id),
+                    ..GemPort::default()
+                });
+            }
+            continue;
+        }
+
+        if line.starts_with('|') {
+            if let Some(gem) = profile.gem_ports.last_mut() {
+                for field in line.split('|') {
+                    match field.split_once(':') {
+                        Some(("Serv-Type", value)) => gem.service_type = value.trim().to_string(),
+                        Some(("Encrypt", value)) => gem.encrypt = value.trim().to_string(),
+                        _ => {}
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(mapping) = parse_gem_mapping(line) {
+            if let Some(gem) = profile.gem_ports.last_mut() {
+                gem.mappings.push(mapping);
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Profile-ID" => {
+                profile.id = value.parse().ok()?;
+                found = true;
+            }
+            "Profile-name" => profile.name = value.to_string(),
+            "Access-type" => profile.access_type = value.to_string(),
+            "FEC upstream switch" => profile.fec_upstream = value.to_string(),
+            "OMCC encrypt switch" => profile.omcc_encrypt = value.to_string(),
+            "Qos mode" => profile.qos_mode = value.to_string(),
+            "Mapping mode" => profile.mapping_mode = value.to_string(),
+            "TR069 management" => profile.tr069_management = value.to_string(),
+            "Binding times" => profile.binding_times = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    found.then_some(profile)
+}
+
+/// Parses a GEM mapping row such as `1      1500  4        ETH  2    -      -     -`.
+fn parse_gem_mapping(line: &str) -> Option<GemMapping> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() != 8 {
+        return None;
+    }
+
+    Some(GemMapping {
+        index: parts[0].parse().ok()?,
+        vlan: parts[1].parse().ok(),
+        priority: parts[2].parse().ok(),
+        port_type: (parts[3] != "-").then(|| parts[3].to_string()),
+        port_id: parts[4].parse().ok(),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServiceProfileSection {
+    None,
+    PortCounts,
+    NativeVlans,
+}
+
+#[must_use]
+pub fn parse_service_profile(output: &str) -> Option<ServiceProfile> {
+    let mut profile = ServiceProfile::default();
+    let mut found = false;
+    let mut section = ServiceProfileSection::None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('-') {
+            continue;
+        }
+
+        if line.starts_with("Port-type") {
+            section = if line.contains("Port-number") {
+                ServiceProfileSection::PortCounts
+            } else if line.contains("Native-VLAN") {
+                ServiceProfileSection::NativeVlans
+            } else {
+                ServiceProfileSection::None
+            };
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "Profile-ID" => {
+                    profile.id = value.parse().ok()?;
+                    found = true;
+                }
+                "Profile-name" => profile.name = value.to_string(),
+                "Access-type" => profile.access_type = value.to_string(),
+                "Binding times" => profile.binding_times = value.parse().ok(),
+                _ => {}
+            }
+            section = ServiceProfileSection::None;
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match section {
+            // Example: "ETH           adaptive        8"
+            ServiceProfileSection::PortCounts if parts.len() == 3 => {
+                profile.ports.push(ServiceProfilePort {
+                    port_type: parts[0].to_string(),
+                    adaptive: parts[1] == "adaptive",
+                    count: parts[1].parse().ok(),
+                    max_adaptive: parts[2].parse().ok(),
+                });
+            }
+            // Example: "ETH       1        20           0"
+            ServiceProfileSection::NativeVlans if parts.len() == 4 => {
+                if let (Ok(port_type), Ok(ont_port), Ok(vlan)) =
+                    (parts[0].parse(), parts[1].parse(), parts[2].parse())
+                {
+                    profile.native_vlans.push(NativeVlan {
+                        port_type,
+                        ont_port,
+                        vlan,
+                        priority: parts[3].parse().unwrap_or(0),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    found.then_some(profile)
+}
+
+#[must_use]
+pub fn parse_dba_profiles(output: &str) -> Vec<DbaProfile> {
+    let mut profiles = Vec::new();
+
+    for line in output.lines() {
+        // Example: "10     4       No          0         0   1048576      412"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 7 {
+            continue;
+        }
+
+        let number = |i: usize| parts[i].parse::<u32>().ok();
+        if let (Some(id), Some(dba_type), Some(fix), Some(assure), Some(max), Some(binding)) = (
+            number(0),
+            number(1),
+            number(3),
+            number(4),
+            number(5),
+            number(6),
+        ) {
+            profiles.push(DbaProfile {
+                id,
+                dba_type,
+                bandwidth_compensate: parts[2].eq_ignore_ascii_case("yes"),
+                fix_kbps: fix,
+                assure_kbps: assure,
+                max_kbps: max,
+                binding_times: binding,
+            });
+        }
+    }
+
+    profiles
+}
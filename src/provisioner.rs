@@ -0,0 +1,211 @@
+//! OLT operations used by reconciliation, migration, bulk provisioning and ONT
+//! search.
+//!
+//! [`Connection`] implements [`Provisioner`] by running the matching commands;
+//! the workflows only depend on the trait so their step order and error
+//! handling can be exercised without a device.
+
+use crate::error::Result;
+use crate::models::{Fsp, OntInfo, OntPortAttribute, OntPortType, ServicePort};
+use crate::ont_info_summary::OntInfoSummary;
+use crate::ssh::{Connection, ServicePortConfig};
+
+pub trait Provisioner {
+    fn ensure_config(&mut self) -> Result<()>;
+
+    fn ensure_interface_gpon(&mut self, frame: u32, slot: u32) -> Result<()>;
+
+    fn display_ont_info_summary(
+        &mut self,
+        frame: u32,
+        slot: u32,
+        port: u32,
+    ) -> Result<OntInfoSummary>;
+
+    fn display_ont_info_summary_frame(&mut self, frame: u32) -> Result<OntInfoSummary>;
+
+    fn display_ont_info_by_sn(&mut self, serial_number: &str) -> Result<Option<OntInfo>>;
+
+    fn display_service_port(&mut self, fsp: Fsp, ont_id: u32) -> Result<Vec<ServicePort>>;
+
+    fn display_ont_port_attribute(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+    ) -> Result<Vec<OntPortAttribute>>;
+
+    fn ont_add(
+        &mut self,
+        port: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32>;
+
+    fn ont_add_with_id(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32>;
+
+    fn ont_delete(&mut self, port: u32, ont_id: u32) -> Result<()>;
+
+    fn ont_modify(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        description: Option<&str>,
+        line_profile_id: Option<u32>,
+        service_profile_id: Option<u32>,
+    ) -> Result<()>;
+
+    fn ont_port_native_vlan(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+        ont_port: u32,
+        vlan: u32,
+        priority: u32,
+    ) -> Result<()>;
+
+    /// Checks once that the VLANs of a batch of service ports exist and have an
+    /// uplink.
+    fn check_service_port_vlans(&mut self, vlans: &[u32]) -> Result<()>;
+
+    /// Adds a service port without looking up its VLAN; callers check the VLANs
+    /// of the batch with [`Provisioner::check_service_port_vlans`] first.
+    fn service_port_add_unchecked(&mut self, config: &ServicePortConfig) -> Result<()>;
+
+    fn service_port_undo(&mut self, service_port_id: u32) -> Result<()>;
+}
+
+impl Provisioner for Connection {
+    fn ensure_config(&mut self) -> Result<()> {
+        Self::ensure_config(self)
+    }
+
+    fn ensure_interface_gpon(&mut self, frame: u32, slot: u32) -> Result<()> {
+        Self::ensure_interface_gpon(self, frame, slot)
+    }
+
+    fn display_ont_info_summary(
+        &mut self,
+        frame: u32,
+        slot: u32,
+        port: u32,
+    ) -> Result<OntInfoSummary> {
+        Self::display_ont_info_summary(self, frame, slot, port)
+    }
+
+    fn display_ont_info_summary_frame(&mut self, frame: u32) -> Result<OntInfoSummary> {
+        Self::display_ont_info_summary_frame(self, frame)
+    }
+
+    fn display_ont_info_by_sn(&mut self, serial_number: &str) -> Result<Option<OntInfo>> {
+        Self::display_ont_info_by_sn(self, serial_number)
+    }
+
+    fn display_service_port(&mut self, fsp: Fsp, ont_id: u32) -> Result<Vec<ServicePort>> {
+        Self::display_service_port(self, fsp, ont_id)
+    }
+
+    fn display_ont_port_attribute(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+    ) -> Result<Vec<OntPortAttribute>> {
+        Self::display_ont_port_attribute(self, port, ont_id, port_type)
+    }
+
+    fn ont_add(
+        &mut self,
+        port: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32> {
+        Self::ont_add(
+            self,
+            port,
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )
+    }
+
+    fn ont_add_with_id(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32> {
+        Self::ont_add_with_id(
+            self,
+            port,
+            ont_id,
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )
+    }
+
+    fn ont_delete(&mut self, port: u32, ont_id: u32) -> Result<()> {
+        Self::ont_delete(self, port, ont_id)
+    }
+
+    fn ont_modify(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        description: Option<&str>,
+        line_profile_id: Option<u32>,
+        service_profile_id: Option<u32>,
+    ) -> Result<()> {
+        Self::ont_modify(
+            self,
+            port,
+            ont_id,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )
+    }
+
+    fn ont_port_native_vlan(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+        ont_port: u32,
+        vlan: u32,
+        priority: u32,
+    ) -> Result<()> {
+        Self::ont_port_native_vlan(self, port, ont_id, port_type, ont_port, vlan, priority)
+    }
+
+    fn check_service_port_vlans(&mut self, vlans: &[u32]) -> Result<()> {
+        Self::check_service_port_vlans(self, vlans)
+    }
+
+    fn service_port_add_unchecked(&mut self, config: &ServicePortConfig) -> Result<()> {
+        Self::service_port_add_unchecked(self, config)
+    }
+
+    fn service_port_undo(&mut self, service_port_id: u32) -> Result<()> {
+        Self::service_port_undo(self, service_port_id)
+    }
+}
@@ -0,0 +1,638 @@
+//! Declarative desired-state reconciliation of ONT configuration.
+//!
+//! Subscribers are described as [`DesiredOnt`] values. [`observe`] reads what the OLT
+//! currently reports, [`plan`] computes an ordered list of [`PlanAction`]s that can be
+//! reviewed before anything is touched, and [`apply`] executes the plan and reports a
+//! result for every action.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::{Error, Result};
+use crate::models::{Fsp, OntPortType, ServicePort};
+use crate::parser::normalize_serial_number;
+use crate::provisioner::Provisioner;
+use crate::ssh::ServicePortConfig;
+
+pub use crate::models::NativeVlan;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct DesiredOnt {
+    /// Serial number, raw (`48575443CB8FBDB4`) or readable (`HWTC-CB8FBDB4`)
+    pub sn: String,
+    /// Frame/Slot/Port the ONT must be registered on
+    pub fsp: Fsp,
+    pub description: String,
+    pub line_profile_id: u32,
+    pub service_profile_id: u32,
+    #[serde(default)]
+    pub native_vlans: Vec<NativeVlan>,
+    #[serde(default)]
+    pub service_ports: Vec<DesiredServicePort>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct DesiredServicePort {
+    pub vlan: u32,
+    pub gemport: u32,
+    pub user_vlan: u32,
+    pub inbound_traffic_table: u32,
+    pub outbound_traffic_table: u32,
+}
+
+impl DesiredServicePort {
+    /// Converts an observed service port into a desired one.
+    ///
+    /// Returns `None` unless the flow carries a GEM index, a user VLAN and both
+    /// traffic tables.
+    #[must_use]
+    pub fn from_service_port(service_port: &ServicePort) -> Option<Self> {
+        Some(Self {
+            vlan: service_port.vlan,
+            gemport: service_port.gemport?,
+            user_vlan: service_port.user_vlan()?,
+            inbound_traffic_table: service_port.inbound_traffic_table?,
+            outbound_traffic_table: service_port.outbound_traffic_table?,
+        })
+    }
+
+    /// Whether `service_port` carries the same flow.
+    #[must_use]
+    pub fn matches(&self, service_port: &ServicePort) -> bool {
+        Self::from_service_port(service_port).as_ref() == Some(self)
+    }
+
+    /// Builds the `service-port` configuration for this flow on a given ONT.
+    pub fn to_config(&self, fsp: Fsp, ont_id: u32) -> Result<ServicePortConfig> {
+        let vlan = i32::try_from(self.vlan)
+            .map_err(|_| Error::CommandFailed(format!("Invalid VLAN {}", self.vlan)))?;
+
+        Ok(ServicePortConfig {
+            vlan,
+            fsp,
+            ont_id,
+            gemport: self.gemport,
+            user_vlan: self.user_vlan,
+            inbound_traffic_table: self.inbound_traffic_table,
+            outbound_traffic_table: self.outbound_traffic_table,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct ObservedOnt {
+    pub fsp: Fsp,
+    pub id: u32,
+    /// Serial number (raw)
+    pub sn: String,
+    pub description: String,
+    /// `None` when the ONT was only seen in the port summary
+    pub line_profile_id: Option<u32>,
+    /// `None` when the ONT was only seen in the port summary
+    pub service_profile_id: Option<u32>,
+    /// Native VLANs read back from the ONT ports; `None` when they were not read
+    pub native_vlans: Option<Vec<NativeVlan>>,
+    pub service_ports: Vec<ServicePort>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ObservedState {
+    pub onts: Vec<ObservedOnt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PlanAction {
+    DeleteServicePort {
+        index: u32,
+        fsp: Fsp,
+        ont_id: u32,
+        vlan: u32,
+    },
+    DeleteOnt {
+        fsp: Fsp,
+        ont_id: u32,
+        sn: String,
+    },
+    ModifyOnt {
+        fsp: Fsp,
+        ont_id: u32,
+        sn: String,
+        description: Option<String>,
+        line_profile_id: Option<u32>,
+        service_profile_id: Option<u32>,
+    },
+    AddOnt {
+        fsp: Fsp,
+        sn: String,
+        description: String,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    },
+    SetNativeVlan {
+        fsp: Fsp,
+        /// Known ONT ID, or `None` when the ONT is added by an earlier action
+        ont_id: Option<u32>,
+        sn: String,
+        native_vlan: NativeVlan,
+    },
+    AddServicePort {
+        fsp: Fsp,
+        /// Known ONT ID, or `None` when the ONT is added by an earlier action
+        ont_id: Option<u32>,
+        sn: String,
+        service_port: DesiredServicePort,
+    },
+}
+
+impl PlanAction {
+    /// Execution phase; actions are applied in ascending phase order so that
+    /// service ports are removed before their ONT and ONTs exist before their
+    /// VLANs and service ports are configured.
+    #[must_use]
+    pub const fn phase(&self) -> u8 {
+        match self {
+            Self::DeleteServicePort { .. } => 0,
+            Self::DeleteOnt { .. } => 1,
+            Self::ModifyOnt { .. } => 2,
+            Self::AddOnt { .. } => 3,
+            Self::SetNativeVlan { .. } => 4,
+            Self::AddServicePort { .. } => 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub actions: Vec<PlanAction>,
+}
+
+impl Plan {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct ActionResult {
+    pub action: PlanAction,
+    /// ONT ID the action applied to, when known
+    pub ont_id: Option<u32>,
+    /// Error message if the action failed or was skipped
+    pub error: Option<String>,
+}
+
+impl ActionResult {
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Reads the current state of every ONT on `ports` and of every desired ONT.
+///
+/// The ports of the desired ONTs are always included in the scope. Desired ONTs
+/// registered outside the scope are still found through their serial number.
+pub fn observe(
+    conn: &mut impl Provisioner,
+    desired: &[DesiredOnt],
+    ports: &[Fsp],
+) -> Result<ObservedState> {
+    conn.ensure_config()?;
+
+    let mut scope: Vec<Fsp> = Vec::new();
+    for fsp in ports.iter().copied().chain(desired.iter().map(|d| d.fsp)) {
+        if !scope.contains(&fsp) {
+            scope.push(fsp);
+        }
+    }
+
+    let mut onts: Vec<ObservedOnt> = Vec::new();
+    for fsp in scope {
+        let summary = conn.display_ont_info_summary(fsp.frame, fsp.slot, fsp.port)?;
+        for port in summary.ports.into_iter().filter(|p| p.fsp == fsp) {
+            for ont in port.onts {
+                onts.push(ObservedOnt {
+                    fsp,
+                    id: ont.id,
+                    sn: ont.sn,
+                    description: ont.description,
+                    line_profile_id: None,
+                    service_profile_id: None,
+                    native_vlans: None,
+                    service_ports: Vec::new(),
+                });
+            }
+        }
+    }
+
+    for ont in desired {
+        let Some(info) = conn.display_ont_info_by_sn(&ont.sn)? else {
+            continue;
+        };
+
+        let observed = ObservedOnt {
+            fsp: info.fsp,
+            id: info.id,
+            sn: info.sn,
+            description: info.description,
+            line_profile_id: Some(info.line_profile_id),
+            service_profile_id: Some(info.service_profile_id),
+            native_vlans: None,
+            service_ports: Vec::new(),
+        };
+
+        let key = normalize_serial_number(&observed.sn);
+        match onts
+            .iter_mut()
+            .find(|o| normalize_serial_number(&o.sn) == key)
+        {
+            Some(existing) => *existing = observed,
+            None => onts.push(observed),
+        }
+    }
+
+    for ont in &mut onts {
+        ont.service_ports = conn.display_service_port(ont.fsp, ont.id)?;
+    }
+
+    for want in desired.iter().filter(|d| !d.native_vlans.is_empty()) {
+        let key = normalize_serial_number(&want.sn);
+        let Some(ont) = onts
+            .iter_mut()
+            .find(|o| o.fsp == want.fsp && normalize_serial_number(&o.sn) == key)
+        else {
+            continue;
+        };
+
+        let mut port_types: Vec<OntPortType> = Vec::new();
+        for native_vlan in &want.native_vlans {
+            if !port_types.contains(&native_vlan.port_type) {
+                port_types.push(native_vlan.port_type);
+            }
+        }
+        ont.native_vlans = Some(read_native_vlans(conn, ont.fsp, ont.id, &port_types)?);
+    }
+
+    conn.ensure_config()?;
+    Ok(ObservedState { onts })
+}
+
+/// Reads the native VLAN of every ONT port of the given types.
+///
+/// Leaves the connection in interface GPON mode for the ONT's board.
+pub fn read_native_vlans(
+    conn: &mut impl Provisioner,
+    fsp: Fsp,
+    ont_id: u32,
+    port_types: &[OntPortType],
+) -> Result<Vec<NativeVlan>> {
+    conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+
+    let mut native_vlans = Vec::new();
+    for &port_type in port_types {
+        for attribute in conn.display_ont_port_attribute(fsp.port, ont_id, port_type)? {
+            if let Some(vlan) = attribute.native_vlan {
+                native_vlans.push(NativeVlan {
+                    port_type,
+                    ont_port: attribute.port_id,
+                    vlan,
+                    priority: attribute.priority.unwrap_or(0),
+                });
+            }
+        }
+    }
+
+    Ok(native_vlans)
+}
+
+/// Computes the actions needed to turn `observed` into `desired`.
+///
+/// Observed ONTs that are not desired are only deleted when `prune` is set.
+/// Native VLANs of existing ONTs are only compared when they were read back.
+#[must_use]
+pub fn plan(desired: &[DesiredOnt], observed: &ObservedState, prune: bool) -> Plan {
+    let mut actions = Vec::new();
+
+    let observed_by_sn: HashMap<String, &ObservedOnt> = observed
+        .onts
+        .iter()
+        .map(|o| (normalize_serial_number(&o.sn), o))
+        .collect();
+    let desired_sns: HashSet<String> = desired
+        .iter()
+        .map(|d| normalize_serial_number(&d.sn))
+        .collect();
+
+    for want in desired {
+        let sn = normalize_serial_number(&want.sn);
+
+        match observed_by_sn.get(&sn) {
+            Some(have) if have.fsp == want.fsp => {
+                plan_modify(&mut actions, want, have, &sn);
+                plan_native_vlans(&mut actions, want, have, &sn);
+                plan_service_ports(&mut actions, want, have, &sn);
+            }
+            Some(have) => {
+                plan_delete(&mut actions, have, &sn);
+                plan_create(&mut actions, want, &sn);
+            }
+            None => plan_create(&mut actions, want, &sn),
+        }
+    }
+
+    if prune {
+        for have in &observed.onts {
+            let sn = normalize_serial_number(&have.sn);
+            if !desired_sns.contains(&sn) {
+                plan_delete(&mut actions, have, &sn);
+            }
+        }
+    }
+
+    actions.sort_by_key(PlanAction::phase);
+    Plan { actions }
+}
+
+fn plan_create(actions: &mut Vec<PlanAction>, want: &DesiredOnt, sn: &str) {
+    actions.push(PlanAction::AddOnt {
+        fsp: want.fsp,
+        sn: sn.to_string(),
+        description: want.description.clone(),
+        line_profile_id: want.line_profile_id,
+        service_profile_id: want.service_profile_id,
+    });
+
+    for native_vlan in &want.native_vlans {
+        actions.push(PlanAction::SetNativeVlan {
+            fsp: want.fsp,
+            ont_id: None,
+            sn: sn.to_string(),
+            native_vlan: native_vlan.clone(),
+        });
+    }
+
+    for service_port in &want.service_ports {
+        actions.push(PlanAction::AddServicePort {
+            fsp: want.fsp,
+            ont_id: None,
+            sn: sn.to_string(),
+            service_port: service_port.clone(),
+        });
+    }
+}
+
+fn plan_delete(actions: &mut Vec<PlanAction>, have: &ObservedOnt, sn: &str) {
+    for service_port in &have.service_ports {
+        actions.push(PlanAction::DeleteServicePort {
+            index: service_port.index,
+            fsp: have.fsp,
+            ont_id: have.id,
+            vlan: service_port.vlan,
+        });
+    }
+
+    actions.push(PlanAction::DeleteOnt {
+        fsp: have.fsp,
+        ont_id: have.id,
+        sn: sn.to_string(),
+    });
+}
+
+fn plan_modify(actions: &mut Vec<PlanAction>, want: &DesiredOnt, have: &ObservedOnt, sn: &str) {
+    let description = (want.description != have.description).then(|| want.description.clone());
+    let line_profile_id =
+        (have.line_profile_id != Some(want.line_profile_id)).then_some(want.line_profile_id);
+    let service_profile_id = (have.service_profile_id != Some(want.service_profile_id))
+        .then_some(want.service_profile_id);
+
+    if description.is_some() || line_profile_id.is_some() || service_profile_id.is_some() {
+        actions.push(PlanAction::ModifyOnt {
+            fsp: have.fsp,
+            ont_id: have.id,
+            sn: sn.to_string(),
+            description,
+            line_profile_id,
+            service_profile_id,
+        });
+    }
+}
+
+fn plan_native_vlans(
+    actions: &mut Vec<PlanAction>,
+    want: &DesiredOnt,
+    have: &ObservedOnt,
+    sn: &str,
+) {
+    let Some(observed) = &have.native_vlans else {
+        return;
+    };
+
+    for native_vlan in &want.native_vlans {
+        if !observed.contains(native_vlan) {
+            actions.push(PlanAction::SetNativeVlan {
+                fsp: have.fsp,
+                ont_id: Some(have.id),
+                sn: sn.to_string(),
+                native_vlan: native_vlan.clone(),
+            });
+        }
+    }
+}
+
+fn plan_service_ports(
+    actions: &mut Vec<PlanAction>,
+    want: &DesiredOnt,
+    have: &ObservedOnt,
+    sn: &str,
+) {
+    for service_port in &have.service_ports {
+        if !want.service_ports.iter().any(|sp| sp.matches(service_port)) {
+            actions.push(PlanAction::DeleteServicePort {
+                index: service_port.index,
+                fsp: have.fsp,
+                ont_id: have.id,
+                vlan: service_port.vlan,
+            });
+        }
+    }
+
+    for service_port in &want.service_ports {
+        if !have.service_ports.iter().any(|sp| service_port.matches(sp)) {
+            actions.push(PlanAction::AddServicePort {
+                fsp: have.fsp,
+                ont_id: Some(have.id),
+                sn: sn.to_string(),
+                service_port: service_port.clone(),
+            });
+        }
+    }
+}
+
+/// Executes `plan` in order and returns one result per action.
+///
+/// The VLANs of the service ports to add are checked once up front; if that
+/// fails nothing is applied and every action reports the error. A failing
+/// action does not stop the run; actions that depend on an ONT whose creation
+/// failed are skipped and reported as errors.
+pub fn apply(conn: &mut impl Provisioner, plan: &Plan) -> Vec<ActionResult> {
+    let mut vlans: Vec<u32> = plan
+        .actions
+        .iter()
+        .filter_map(|action| match action {
+            PlanAction::AddServicePort { service_port, .. } => Some(service_port.vlan),
+            _ => None,
+        })
+        .collect();
+    vlans.sort_unstable();
+    vlans.dedup();
+
+    if !vlans.is_empty() {
+        let checked = conn
+            .ensure_config()
+            .and_then(|()| conn.check_service_port_vlans(&vlans));
+        if let Err(err) = checked {
+            return plan
+                .actions
+                .iter()
+                .map(|action| ActionResult {
+                    action: action.clone(),
+                    ont_id: None,
+                    error: Some(err.to_string()),
+                })
+                .collect();
+        }
+    }
+
+    let mut added: HashMap<String, u32> = HashMap::new();
+    let mut failed: HashSet<String> = HashSet::new();
+    let mut results = Vec::with_capacity(plan.actions.len());
+
+    for action in &plan.actions {
+        let outcome = apply_action(conn, action, &added, &failed);
+
+        if let PlanAction::AddOnt { sn, .. } = action {
+            match &outcome {
+                Ok(Some(id)) => {
+                    added.insert(sn.clone(), *id);
+                }
+                _ => {
+                    failed.insert(sn.clone());
+                }
+            }
+        }
+
+        results.push(match outcome {
+            Ok(ont_id) => ActionResult {
+                action: action.clone(),
+                ont_id,
+                error: None,
+            },
+            Err(err) => ActionResult {
+                action: action.clone(),
+                ont_id: None,
+                error: Some(err.to_string()),
+            },
+        });
+    }
+
+    results
+}
+
+fn apply_action(
+    conn: &mut impl Provisioner,
+    action: &PlanAction,
+    added: &HashMap<String, u32>,
+    failed: &HashSet<String>,
+) -> Result<Option<u32>> {
+    let resolve = |sn: &String, ont_id: Option<u32>| -> Result<u32> {
+        if let Some(id) = ont_id.or_else(|| added.get(sn).copied()) {
+            return Ok(id);
+        }
+        if failed.contains(sn) {
+            return Err(Error::CommandFailed(format!("ONT {sn} was not added")));
+        }
+        Err(Error::NotFound)
+    };
+
+    match action {
+        PlanAction::DeleteServicePort { index, ont_id, .. } => {
+            conn.ensure_config()?;
+            conn.service_port_undo(*index)?;
+            Ok(Some(*ont_id))
+        }
+        PlanAction::DeleteOnt { fsp, ont_id, .. } => {
+            conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+            conn.ont_delete(fsp.port, *ont_id)?;
+            Ok(Some(*ont_id))
+        }
+        PlanAction::ModifyOnt {
+            fsp,
+            ont_id,
+            description,
+            line_profile_id,
+            service_profile_id,
+            ..
+        } => {
+            conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+            conn.ont_modify(
+                fsp.port,
+                *ont_id,
+                description.as_deref(),
+                *line_profile_id,
+                *service_profile_id,
+            )?;
+            Ok(Some(*ont_id))
+        }
+        PlanAction::AddOnt {
+            fsp,
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        } => {
+            conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+            let id = conn.ont_add(
+                fsp.port,
+                sn,
+                description,
+                *line_profile_id,
+                *service_profile_id,
+            )?;
+            Ok(Some(id))
+        }
+        PlanAction::SetNativeVlan {
+            fsp,
+            ont_id,
+            sn,
+            native_vlan,
+        } => {
+            let id = resolve(sn, *ont_id)?;
+            conn.ensure_interface_gpon(fsp.frame, fsp.slot)?;
+            conn.ont_port_native_vlan(
+                fsp.port,
+                id,
+                native_vlan.port_type,
+                native_vlan.ont_port,
+                native_vlan.vlan,
+                native_vlan.priority,
+            )?;
+            Ok(Some(id))
+        }
+        PlanAction::AddServicePort {
+            fsp,
+            ont_id,
+            sn,
+            service_port,
+        } => {
+            let id = resolve(sn, *ont_id)?;
+            let config = service_port.to_config(*fsp, id)?;
+            conn.ensure_config()?;
+            conn.service_port_add_unchecked(&config)?;
+            Ok(Some(id))
+        }
+    }
+}
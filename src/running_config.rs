@@ -0,0 +1,406 @@
+//! Typed model of `display current-configuration`.
+//!
+//! Only the parts needed to audit provisioning are modelled: VLANs and their
+//! uplink ports, GPON line and service profiles, ONTs with their native VLANs,
+//! and service ports. Other lines are ignored.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::{Fsp, NativeVlan, ServicePort};
+use crate::parser::strip_pager;
+use crate::profile::{GemMapping, GemPort, LineProfile, ServiceProfile, ServiceProfilePort, Tcont};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct RunningConfig {
+    pub sysname: Option<String>,
+    pub vlans: Vec<ConfigVlan>,
+    /// Uplink bindings from `port vlan <vlan> <frame>/<slot> <port>`
+    pub port_vlans: Vec<ConfigPortVlan>,
+    pub line_profiles: Vec<LineProfile>,
+    pub service_profiles: Vec<ServiceProfile>,
+    pub onts: Vec<ConfigOnt>,
+    pub service_ports: Vec<ServicePort>,
+}
+
+impl RunningConfig {
+    #[must_use]
+    pub fn ont(&self, fsp: Fsp, id: u32) -> Option<&ConfigOnt> {
+        self.onts.iter().find(|o| o.fsp == fsp && o.id == id)
+    }
+
+    /// Returns the service ports configured for an ONT.
+    #[must_use]
+    pub fn service_ports_for(&self, fsp: Fsp, ont_id: u32) -> Vec<&ServicePort> {
+        self.service_ports
+            .iter()
+            .filter(|sp| sp.fsp == fsp && sp.ont_id == Some(ont_id))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ConfigVlan {
+    pub id: u32,
+    /// VLAN type (e.g. `smart`, `standard`)
+    pub vlan_type: String,
+}
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct ConfigPortVlan {
+    pub vlan: u32,
+    pub fsp: Fsp,
+}
+
+/// ONT from an `ont add` line inside `interface gpon`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct ConfigOnt {
+    pub fsp: Fsp,
+    pub id: u32,
+    /// Authentication mode (e.g. `sn-auth`, `password-auth`, `loid-auth`)
+    pub auth: String,
+    /// Serial number (raw)
+    pub sn: Option<String>,
+    pub line_profile_id: Option<u32>,
+    pub service_profile_id: Option<u32>,
+    pub description: Option<String>,
+    /// Native VLANs from `ont port native-vlan`
+    pub native_vlans: Vec<NativeVlan>,
+}
+
+enum Scope {
+    Global,
+    LineProfile,
+    ServiceProfile,
+    InterfaceGpon { frame: u32, slot: u32 },
+}
+
+#[must_use]
+pub fn parse_running_config(output: &str) -> RunningConfig {
+    let mut config = RunningConfig::default();
+    let mut scope = Scope::Global;
+
+    for line in output.lines() {
+        let tokens = tokenize(&strip_pager(line));
+        let Some(first) = tokens.first() else {
+            continue;
+        };
+        let words: Vec<&str> = tokens.iter().map(String::as_str).collect();
+
+        if first.starts_with('[') || first == "#" {
+            scope = Scope::Global;
+            continue;
+        }
+        if first == "quit" {
+            scope = Scope::Global;
+            continue;
+        }
+
+        match (&scope, words.as_slice()) {
+            (_, ["sysname", name]) => config.sysname = Some((*name).to_string()),
+            (_, ["vlan", ..]) => parse_vlan_line(&words, &mut config.vlans),
+            (_, ["port", "vlan", ..]) if !matches!(scope, Scope::ServiceProfile) => {
+                parse_port_vlan_line(&words, &mut config.port_vlans);
+            }
+            (_, ["ont-lineprofile", "gpon", ..]) => {
+                if let Some(id) = value_after(&words, "profile-id") {
+                    config.line_profiles.push(LineProfile {
+                        id,
+                        name: string_after(&words, "profile-name").unwrap_or_default(),
+                        ..LineProfile::default()
+                    });
+                    scope = Scope::LineProfile;
+                }
+            }
+            (_, ["ont-srvprofile", "gpon", ..]) => {
+                if let Some(id) = value_after(&words, "profile-id") {
+                    config.service_profiles.push(ServiceProfile {
+                        id,
+                        name: string_after(&words, "profile-name").unwrap_or_default(),
+                        ..ServiceProfile::default()
+                    });
+                    scope = Scope::ServiceProfile;
+                }
+            }
+            (_, ["interface", "gpon", fs]) => {
+                if let Some((frame, slot)) = fs.split_once('/') {
+                    if let (Ok(frame), Ok(slot)) = (frame.parse(), slot.parse()) {
+                        scope = Scope::InterfaceGpon { frame, slot };
+                    }
+                }
+            }
+            (_, ["service-port", ..]) => {
+                if let Some(service_port) = parse_service_port_line(&words) {
+                    config.service_ports.push(service_port);
+                }
+            }
+            (Scope::LineProfile, _) => {
+                if let Some(profile) = config.line_profiles.last_mut() {
+                    parse_line_profile_line(&words, profile);
+                }
+            }
+            (Scope::ServiceProfile, _) => {
+                if let Some(profile) = config.service_profiles.last_mut() {
+                    parse_service_profile_line(&words, profile);
+                }
+            }
+            (Scope::InterfaceGpon { frame, slot }, _) => {
+                parse_interface_line(&words, *frame, *slot, &mut config.onts);
+            }
+            (Scope::Global, _) => {}
+        }
+    }
+
+    config
+}
+
+/// Splits a configuration line on whitespace, keeping double-quoted strings
+/// (without their quotes) as single tokens.
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_token = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_token = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+fn value_after<T: std::str::FromStr>(words: &[&str], key: &str) -> Option<T> {
+    words
+        .iter()
+        .position(|w| *w == key)
+        .and_then(|i| words.get(i + 1))
+        .and_then(|v| v.parse().ok())
+}
+
+fn string_after(words: &[&str], key: &str) -> Option<String> {
+    value_after(words, key)
+}
+
+/// Parses `vlan <id> <type>` and `vlan <first> to <last> <type>`.
+fn parse_vlan_line(words: &[&str], vlans: &mut Vec<ConfigVlan>) {
+    let (ids, vlan_type) = match words {
+        ["vlan", first, "to", last, vlan_type, ..] => match (first.parse(), last.parse()) {
+            (Ok(first), Ok(last)) => (first..=last, vlan_type),
+            _ => return,
+        },
+        ["vlan", id, vlan_type, ..] => match id.parse() {
+            Ok(id) => (id..=id, vlan_type),
+            Err(_) => return,
+        },
+        _ => return,
+    };
+
+    vlans.extend(ids.map(|id| ConfigVlan {
+        id,
+        vlan_type: (*vlan_type).to_string(),
+    }));
+}
+
+/// Parses `port vlan <id> <frame>/<slot> <port>` and the `to` range form.
+fn parse_port_vlan_line(words: &[&str], port_vlans: &mut Vec<ConfigPortVlan>) {
+    let (ids, fs, port) = match words {
+        ["port", "vlan", first, "to", last, fs, port, ..] => match (first.parse(), last.parse()) {
+            (Ok(first), Ok(last)) => (first..=last, fs, port),
+            _ => return,
+        },
+        ["port", "vlan", id, fs, port, ..] => match id.parse() {
+            Ok(id) => (id..=id, fs, port),
+            Err(_) => return,
+        },
+        _ => return,
+    };
+    let Some(fsp) = Fsp::parse(&format!("{fs}/{port}")) else {
+        return;
+    };
+
+    port_vlans.extend(ids.map(|vlan| ConfigPortVlan { vlan, fsp }));
+}
+
+fn parse_line_profile_line(words: &[&str], profile: &mut LineProfile) {
+    match words {
+        // Example: "tcont 4 dba-profile-id 10"
+        ["tcont", id, ..] => {
+            if let (Ok(id), Some(dba_profile_id)) =
+                (id.parse(), value_after(words, "dba-profile-id"))
+            {
+                profile.tconts.push(Tcont { id, dba_profile_id });
+            }
+        }
+        // Example: "gem add 20 eth tcont 4 encrypt off"
+        ["gem", "add", index, service_type, ..] => {
+            if let (Ok(index), Some(tcont_id)) = (index.parse(), value_after(words, "tcont")) {
+                profile.gem_ports.push(GemPort {
+                    index,
+                    tcont_id,
+                    service_type: service_type.to_ascii_uppercase(),
+                    encrypt: string_after(words, "encrypt").unwrap_or_else(|| "off".to_string()),
+                    mappings: Vec::new(),
+                });
+            }
+        }
+        // Example: "gem mapping 20 0 vlan 20 priority 0"
+        ["gem", "mapping", gem, index, ..] => {
+            let (Ok(gem), Ok(index)) = (gem.parse::<u32>(), index.parse()) else {
+                return;
+            };
+            if let Some(gem) = profile.gem_ports.iter_mut().find(|g| g.index == gem) {
+                gem.mappings.push(GemMapping {
+                    index,
+                    vlan: value_after(words, "vlan"),
+                    priority: value_after(words, "priority"),
+                    port_type: None,
+                    port_id: None,
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_service_profile_line(words: &[&str], profile: &mut ServiceProfile) {
+    match words {
+        // Example: "ont-port pots adaptive 32 eth adaptive 8" or "ont-port eth 4"
+        ["ont-port", rest @ ..] => {
+            let mut i = 0;
+            while i + 1 < rest.len() {
+                let port_type = rest[i].to_ascii_uppercase();
+                if rest[i + 1] == "adaptive" {
+                    let max_adaptive = rest.get(i + 2).and_then(|v| v.parse().ok());
+                    profile.ports.push(ServiceProfilePort {
+                        port_type,
+                        adaptive: true,
+                        count: None,
+                        max_adaptive,
+                    });
+                    i += if max_adaptive.is_some() { 3 } else { 2 };
+                } else {
+                    profile.ports.push(ServiceProfilePort {
+                        port_type,
+                        adaptive: false,
+                        count: rest[i + 1].parse().ok(),
+                        max_adaptive: None,
+                    });
+                    i += 2;
+                }
+            }
+        }
+        // Example: "port native-vlan eth 1 vlan 20 priority 0"
+        ["port", "native-vlan", port_type, ont_port, ..] => {
+            if let (Ok(port_type), Ok(ont_port), Some(vlan)) = (
+                port_type.parse(),
+                ont_port.parse(),
+                value_after(words, "vlan"),
+            ) {
+                profile.native_vlans.push(NativeVlan {
+                    port_type,
+                    ont_port,
+                    vlan,
+                    priority: value_after(words, "priority").unwrap_or(0),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_interface_line(words: &[&str], frame: u32, slot: u32, onts: &mut Vec<ConfigOnt>) {
+    match words {
+        // Example: "ont add 0 1 sn-auth "48575443CB8FBDB4" omci ont-lineprofile-id 60 ..."
+        ["ont", "add", port, id, auth, rest @ ..] => {
+            let (Ok(port), Ok(id)) = (port.parse(), id.parse()) else {
+                return;
+            };
+            onts.push(ConfigOnt {
+                fsp: Fsp { frame, slot, port },
+                id,
+                auth: (*auth).to_string(),
+                sn: (*auth == "sn-auth")
+                    .then(|| rest.first().map(|sn| (*sn).to_string()))
+                    .flatten(),
+                line_profile_id: value_after(words, "ont-lineprofile-id"),
+                service_profile_id: value_after(words, "ont-srvprofile-id"),
+                description: string_after(words, "desc"),
+                native_vlans: Vec::new(),
+            });
+        }
+        // Example: "ont port native-vlan 0 1 eth 1 vlan 20 priority 0"
+        ["ont", "port", "native-vlan", port, id, port_type, ont_port, ..] => {
+            let (Ok(port), Ok(id)) = (port.parse::<u32>(), id.parse::<u32>()) else {
+                return;
+            };
+            let (Ok(port_type), Ok(ont_port), Some(vlan)) = (
+                port_type.parse(),
+                ont_port.parse(),
+                value_after(words, "vlan"),
+            ) else {
+                return;
+            };
+            if let Some(ont) = onts
+                .iter_mut()
+                .find(|o| o.fsp == Fsp { frame, slot, port } && o.id == id)
+            {
+                ont.native_vlans.push(NativeVlan {
+                    port_type,
+                    ont_port,
+                    vlan,
+                    priority: value_after(words, "priority").unwrap_or(0),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parses a `service-port <index> vlan <vlan> gpon <f/s/p> ont <id> gemport <gem> ...` line.
+fn parse_service_port_line(words: &[&str]) -> Option<ServicePort> {
+    let index = words.get(1)?.parse().ok()?;
+    let port_type = ["gpon", "epon", "eth"]
+        .into_iter()
+        .find(|t| words.contains(t))?;
+    let user_vlan: Option<u32> = value_after(words, "user-vlan");
+
+    Some(ServicePort {
+        index,
+        vlan: value_after(words, "vlan")?,
+        port_type: port_type.to_string(),
+        fsp: Fsp::parse(&string_after(words, port_type)?)?,
+        ont_id: value_after(words, "ont"),
+        gemport: value_after(words, "gemport"),
+        flow_type: if user_vlan.is_some() { "vlan" } else { "" }.to_string(),
+        flow_para: user_vlan.map(|v| v.to_string()).unwrap_or_default(),
+        inbound_traffic_table: words
+            .iter()
+            .position(|w| *w == "inbound")
+            .and_then(|i| value_after(&words[i..], "index")),
+        outbound_traffic_table: words
+            .iter()
+            .position(|w| *w == "outbound")
+            .and_then(|i| value_after(&words[i..], "index")),
+        ..ServicePort::default()
+    })
+}
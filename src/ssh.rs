@@ -1,14 +1,46 @@
 use ssh2::Session;
 use std::io::{ErrorKind, Read, Write};
 use std::net::TcpStream;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
 
 use crate::alarm::{parse_active_alarms_detail, parse_active_alarms_list, ActiveAlarms};
+use crate::configuration::{save_status, ConfigurationFilter, SaveStatus, SAVE_TIMEOUT};
 use crate::error::{Error, Result};
-use crate::models::{Fsp, OntInfo, OpticalInfo, ServicePort};
+use crate::models::{
+    BoardDetail, BoardSlot, Fsp, OntEthPortState, OntInfo, OntInfoList, OntInfoListEntry,
+    OntIpConfig, OntOpticalLevels, OntPortAttribute, OntPortType, OntRegisterRecord, OntVersion,
+    OntWanInfo, OpticalInfo, ServicePort, ServicePortTable,
+};
 use crate::ont_info_summary::{parse_ont_info_summary, OntInfoSummary};
+use crate::ont_statistics::{parse_ont_counters, parse_ont_traffic, OntCounters, OntTraffic};
 use crate::parser::{
-    check_for_failure, extract_ont_id, parse_ont_autofind, parse_ont_info, parse_optical_info,
-    parse_service_ports,
+    check_for_failure, extract_next_free_service_port_index, extract_ont_id, parse_board_detail,
+    parse_display_board, parse_ont_autofind, parse_ont_eth_port_states, parse_ont_info,
+    parse_ont_info_list, parse_ont_ipconfig, parse_ont_optical_levels, parse_ont_port_attributes,
+    parse_ont_register_info, parse_ont_versions, parse_ont_wan_info, parse_optical_info,
+    parse_service_port_table, parse_service_ports, quote_argument,
+};
+use crate::pon_port::{
+    parse_pon_optical_module, parse_pon_port_info, parse_pon_port_state, PonOpticalModule,
+    PonPortInfo, PonPortState,
+};
+use crate::profile::{
+    dba_profile_add_command, parse_dba_profiles, parse_line_profile, parse_profile_summaries,
+    parse_service_profile, DbaBandwidth, DbaProfile, LineProfile, PortCount, ProfileSummary,
+    ServiceProfile,
+};
+use crate::running_config::{parse_running_config, RunningConfig};
+use crate::system::{
+    parse_occupancy, parse_system_patches, parse_system_version, parse_temperature, parse_uptime,
+    BoardHealth, SystemInfo, SystemPatch, SystemVersion,
+};
+use crate::traffic_table::{
+    find_traffic_table, free_traffic_table_index, parse_traffic_tables, TrafficTable,
+    TrafficTableConfig,
+};
+use crate::vlan::{
+    check_service_port_vlans, parse_vlan_detail, parse_vlans, Vlan, VlanDetail, VlanType,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,6 +53,41 @@ pub enum SessionLevel {
     Config = 2,
     /// Interface GPON level ((config-if-gpon-F/S)#)
     InterfaceGpon = 3,
+    /// GPON line profile level ((config-gpon-lineprofile-N)#)
+    GponLineProfile = 4,
+    /// GPON service profile level ((config-gpon-srvprofile-N)#)
+    GponServiceProfile = 5,
+}
+
+impl SessionLevel {
+    const fn profile_kind(self) -> Option<&'static str> {
+        match self {
+            Self::GponLineProfile => Some("lineprofile"),
+            Self::GponServiceProfile => Some("srvprofile"),
+            _ => None,
+        }
+    }
+
+    /// Returns the prompt of profile `id` for the profile levels.
+    #[must_use]
+    pub fn profile_prompt(self, id: u32) -> Option<String> {
+        let kind = self.profile_kind()?;
+        Some(format!("(config-gpon-{kind}-{id})#"))
+    }
+
+    /// Returns the command entering profile `id` for the profile levels,
+    /// creating the profile as `name` if it does not exist.
+    pub fn profile_enter_command(self, id: u32, name: Option<&str>) -> Result<String> {
+        let kind = self
+            .profile_kind()
+            .ok_or_else(|| Error::InvalidContext("Not a profile level".to_string()))?;
+
+        let mut cmd = format!("ont-{kind} gpon profile-id {id}");
+        if let Some(name) = name {
+            cmd = format!("{cmd} profile-name {}", quote_argument(name)?);
+        }
+        Ok(cmd)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -28,6 +95,7 @@ pub struct SessionContext {
     pub level: SessionLevel,
     pub frame: Option<u32>,
     pub slot: Option<u32>,
+    pub profile_id: Option<u32>,
 }
 
 impl SessionContext {
@@ -36,6 +104,7 @@ impl SessionContext {
             level: SessionLevel::Root,
             frame: None,
             slot: None,
+            profile_id: None,
         }
     }
 }
@@ -50,6 +119,29 @@ pub struct ServicePortConfig {
     pub outbound_traffic_table: u32,
 }
 
+/// Message of the error returned when the OLT is too busy to run a command
+const SYSTEM_BUSY: &str = "System is busy, please retry after a while";
+
+/// Maps a board rejecting a display command to a missing metric.
+///
+/// Only `Failure:` lines and "Parameter error" count as a rejection; a busy
+/// system and session errors are returned.
+fn board_metric<T>(result: Result<Option<T>>) -> Result<Option<T>> {
+    match result {
+        Err(Error::CommandFailed(msg)) if !msg.starts_with("System is busy") => Ok(None),
+        // `check_for_failure` reports "Parameter error" as an invalid serial number
+        Err(Error::InvalidSerialNumber) => Ok(None),
+        result => result,
+    }
+}
+
+fn service_port_arguments(config: &ServicePortConfig) -> String {
+    format!(
+        "vlan {} gpon {}/{}/{} ont {} gemport {} multi-service user-vlan {} tag-transform translate inbound traffic-table index {} outbound traffic-table index {}",
+        config.vlan, config.fsp.frame, config.fsp.slot, config.fsp.port, config.ont_id, config.gemport, config.user_vlan, config.inbound_traffic_table, config.outbound_traffic_table
+    )
+}
+
 pub struct Connection {
     #[allow(dead_code)]
     session: Session,
@@ -140,7 +232,9 @@ impl Connection {
     pub fn ensure_config(&mut self) -> Result<()> {
         match self.context.level {
             SessionLevel::Config => Ok(()),
-            SessionLevel::InterfaceGpon => self.quit(),
+            SessionLevel::InterfaceGpon
+            | SessionLevel::GponLineProfile
+            | SessionLevel::GponServiceProfile => self.quit(),
             SessionLevel::Enable => self.config(),
             SessionLevel::Root => {
                 self.enable()?;
@@ -149,6 +243,18 @@ impl Connection {
         }
     }
 
+    pub fn ensure_interface_gpon(&mut self, frame: u32, slot: u32) -> Result<()> {
+        if self.context.level == SessionLevel::InterfaceGpon
+            && self.context.frame == Some(frame)
+            && self.context.slot == Some(slot)
+        {
+            return Ok(());
+        }
+
+        self.ensure_config()?;
+        self.interface_gpon(frame, slot)
+    }
+
     pub fn ping(&mut self) -> Result<()> {
         let _ = self.execute("")?;
         Ok(())
@@ -188,6 +294,13 @@ impl Connection {
                 self.context.frame = None;
                 self.context.slot = None;
             }
+            SessionLevel::GponLineProfile | SessionLevel::GponServiceProfile => {
+                self.channel.write_all(b"quit\n")?;
+                self.channel.flush()?;
+                self.read_until_prompt("(config)#")?;
+                self.context.level = SessionLevel::Config;
+                self.context.profile_id = None;
+            }
             SessionLevel::Config => {
                 self.channel.write_all(b"quit\n")?;
                 self.channel.flush()?;
@@ -246,6 +359,24 @@ impl Connection {
         Ok(parse_ont_info(&output))
     }
 
+    /// Runs `display ont info by-desc` and returns the ONTs whose description
+    /// is exactly `description`.
+    pub fn display_ont_info_by_desc(&mut self, description: &str) -> Result<Vec<OntInfoListEntry>> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("display ont info by-desc {}", quote_argument(description)?);
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        if output.contains("The required ONT does not exist") {
+            return Ok(Vec::new());
+        }
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_info_list(&output).onts)
+    }
+
     pub fn display_ont_info_by_mac(&mut self, mac_address: &str) -> Result<Option<OntInfo>> {
         if self.context.level != SessionLevel::Config {
             return Err(Error::InvalidContext("Must be in config mode".to_string()));
@@ -270,44 +401,206 @@ impl Connection {
         slot: u32,
         port: u32,
     ) -> Result<OntInfoSummary> {
+        self.ont_info_summary(&format!("{frame}/{slot}/{port}"))
+    }
+
+    /// Runs `display ont info summary <frame>/<slot>` for every port of a board.
+    pub fn display_ont_info_summary_board(
+        &mut self,
+        frame: u32,
+        slot: u32,
+    ) -> Result<OntInfoSummary> {
+        self.ont_info_summary(&format!("{frame}/{slot}"))
+    }
+
+    /// Runs `display ont info summary <frame>` for every port of a frame.
+    pub fn display_ont_info_summary_frame(&mut self, frame: u32) -> Result<OntInfoSummary> {
+        self.ont_info_summary(&frame.to_string())
+    }
+
+    fn ont_info_summary(&mut self, selector: &str) -> Result<OntInfoSummary> {
         if self.context.level != SessionLevel::Config {
             return Err(Error::InvalidContext("Must be in config mode".to_string()));
         }
 
-        let cmd = format!("display ont info summary {frame}/{slot}/{port}");
+        let cmd = format!("display ont info summary {selector}");
         let output = self.execute_command(&cmd, "(config)#")?;
 
         Ok(parse_ont_info_summary(&output))
     }
 
+    /// Runs `display ont info <F/S/P> all` and returns every ONT on the port.
+    pub fn display_ont_info_all(&mut self, fsp: Fsp) -> Result<OntInfoList> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("display ont info {fsp} all");
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        if output.contains("There is no ONT available") {
+            return Ok(OntInfoList {
+                fsp,
+                ..OntInfoList::default()
+            });
+        }
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_info_list(&output))
+    }
+
+    /// Runs `display ont version` for a single ONT.
+    pub fn display_ont_version(&mut self, fsp: Fsp, ont_id: u32) -> Result<Option<OntVersion>> {
+        let versions = self.display_ont_versions(fsp, &ont_id.to_string())?;
+        Ok(versions.into_iter().find(|v| v.ont_id == ont_id))
+    }
+
+    /// Runs `display ont version` for every ONT on the port.
+    pub fn display_ont_version_all(&mut self, fsp: Fsp) -> Result<Vec<OntVersion>> {
+        self.display_ont_versions(fsp, "all")
+    }
+
+    fn display_ont_versions(&mut self, fsp: Fsp, selector: &str) -> Result<Vec<OntVersion>> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!(
+            "display ont version {} {} {} {selector}",
+            fsp.frame, fsp.slot, fsp.port
+        );
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        if output.contains("The required ONT does not exist")
+            || output.contains("There is no ONT available")
+        {
+            return Ok(Vec::new());
+        }
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_versions(&output))
+    }
+
     pub fn display_ont_optical_info(
         &mut self,
         port: u32,
         ont_id: u32,
     ) -> Result<Option<OpticalInfo>> {
-        if self.context.level != SessionLevel::InterfaceGpon {
-            return Err(Error::InvalidContext(
-                "Must be in interface gpon mode".to_string(),
-            ));
-        }
-
-        let frame = self
-            .context
-            .frame
-            .ok_or_else(|| Error::InvalidContext("Frame not set in context".to_string()))?;
-        let slot = self
-            .context
-            .slot
-            .ok_or_else(|| Error::InvalidContext("Slot not set in context".to_string()))?;
+        let prompt = self.interface_gpon_prompt()?;
 
         let cmd = format!("display ont optical-info {port} {ont_id}");
-        let prompt = format!("(config-if-gpon-{frame}/{slot})#");
         let output = self.execute_command(&cmd, &prompt)?;
 
         check_for_failure(&output)?;
         Ok(parse_optical_info(&output))
     }
 
+    /// Runs `display ont optical-info <port> all` for every ONT on the port.
+    pub fn display_ont_optical_info_all(&mut self, port: u32) -> Result<Vec<OntOpticalLevels>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display ont optical-info {port} all");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_optical_levels(&output))
+    }
+
+    /// Runs `display ont wan-info <port> <ont_id>`.
+    pub fn display_ont_wan_info(&mut self, port: u32, ont_id: u32) -> Result<Vec<OntWanInfo>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display ont wan-info {port} {ont_id}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_wan_info(&output))
+    }
+
+    /// Runs `display ont ipconfig <port> <ont_id>`.
+    pub fn display_ont_ipconfig(&mut self, port: u32, ont_id: u32) -> Result<Vec<OntIpConfig>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display ont ipconfig {port} {ont_id}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_ipconfig(&output))
+    }
+
+    /// Reads the cumulative traffic and line-quality counters of an ONT.
+    ///
+    /// Runs `display statistics ont` and `display statistics ont-line-quality`
+    /// and merges both into one sample.
+    pub fn display_ont_counters(&mut self, port: u32, ont_id: u32) -> Result<OntCounters> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display statistics ont {port} {ont_id}");
+        let output = self.execute_command(&cmd, &prompt)?;
+        check_for_failure(&output)?;
+        let traffic = parse_ont_counters(&output);
+
+        let cmd = format!("display statistics ont-line-quality {port} {ont_id}");
+        let output = self.execute_command(&cmd, &prompt)?;
+        check_for_failure(&output)?;
+
+        Ok(traffic.merge(parse_ont_counters(&output)))
+    }
+
+    /// Runs `display ont traffic <port> <ont_id>`.
+    pub fn display_ont_traffic(&mut self, port: u32, ont_id: u32) -> Result<Option<OntTraffic>> {
+        let traffic = self.display_ont_traffic_table(port, &ont_id.to_string())?;
+        Ok(traffic.into_iter().find(|t| t.ont_id == ont_id))
+    }
+
+    /// Runs `display ont traffic <port> all`.
+    pub fn display_ont_traffic_all(&mut self, port: u32) -> Result<Vec<OntTraffic>> {
+        self.display_ont_traffic_table(port, "all")
+    }
+
+    fn display_ont_traffic_table(&mut self, port: u32, selector: &str) -> Result<Vec<OntTraffic>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display ont traffic {port} {selector}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_traffic(&output))
+    }
+
+    /// Runs `display port state <port>` on the current board.
+    pub fn display_port_state(&mut self, port: u32) -> Result<Option<PonPortState>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display port state {port}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_pon_port_state(&output))
+    }
+
+    /// Runs `display port info <port>` on the current board.
+    pub fn display_port_info(&mut self, port: u32) -> Result<Option<PonPortInfo>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display port info {port}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_pon_port_info(&output))
+    }
+
+    /// Runs `display port ddm-info <port>` and returns the OLT-side optical module.
+    pub fn display_port_ddm_info(&mut self, port: u32) -> Result<Option<PonOpticalModule>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display port ddm-info {port}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_pon_optical_module(&output))
+    }
+
     pub fn display_service_port(&mut self, fsp: Fsp, ont_id: u32) -> Result<Vec<ServicePort>> {
         if self.context.level != SessionLevel::Config {
             return Err(Error::InvalidContext("Must be in config mode".to_string()));
@@ -323,6 +616,39 @@ impl Connection {
         Ok(parse_service_ports(&output))
     }
 
+    /// Runs `display service-port all`.
+    pub fn display_service_port_all(&mut self) -> Result<ServicePortTable> {
+        self.display_service_port_table("all")
+    }
+
+    /// Runs `display service-port vlan <vlan>`.
+    pub fn display_service_port_vlan(&mut self, vlan: u32) -> Result<ServicePortTable> {
+        self.display_service_port_table(&format!("vlan {vlan}"))
+    }
+
+    /// Runs `display service-port port <F/S/P>`, covering every ONT on the port.
+    pub fn display_service_port_by_port(&mut self, fsp: Fsp) -> Result<ServicePortTable> {
+        self.display_service_port_table(&format!("port {fsp}"))
+    }
+
+    /// Runs `display service-port <index>`.
+    pub fn display_service_port_index(&mut self, index: u32) -> Result<Option<ServicePort>> {
+        let table = self.display_service_port_table(&index.to_string())?;
+        Ok(table.ports.into_iter().find(|p| p.index == index))
+    }
+
+    fn display_service_port_table(&mut self, selector: &str) -> Result<ServicePortTable> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("display service-port {selector}");
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        check_for_failure(&output)?;
+        Ok(parse_service_port_table(&output))
+    }
+
     pub fn ont_add(
         &mut self,
         port: u32,
@@ -331,107 +657,271 @@ impl Connection {
         line_profile_id: u32,
         service_profile_id: u32,
     ) -> Result<u32> {
-        if self.context.level != SessionLevel::InterfaceGpon {
-            return Err(Error::InvalidContext(
-                "Must be in interface gpon mode".to_string(),
-            ));
-        }
+        self.run_ont_add(
+            port,
+            None,
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )?
+        .ok_or_else(|| Error::ParseError("Failed to extract ONT ID from output".to_string()))
+    }
 
-        let frame = self
-            .context
-            .frame
-            .ok_or_else(|| Error::InvalidContext("Frame not set in context".to_string()))?;
-        let slot = self
-            .context
-            .slot
-            .ok_or_else(|| Error::InvalidContext("Slot not set in context".to_string()))?;
+    /// Adds an ONT with an explicitly chosen ONT ID instead of letting the OLT pick one.
+    pub fn ont_add_with_id(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32> {
+        let id = self.run_ont_add(
+            port,
+            Some(ont_id),
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )?;
+        Ok(id.unwrap_or(ont_id))
+    }
+
+    /// Runs `ont add` and returns the ONT ID reported by the OLT, if any.
+    fn run_ont_add(
+        &mut self,
+        port: u32,
+        ont_id: Option<u32>,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<Option<u32>> {
+        let prompt = self.interface_gpon_prompt()?;
 
         let sn = sn.split_whitespace().next().unwrap_or(sn);
+        let target = ont_id.map_or_else(|| port.to_string(), |id| format!("{port} {id}"));
         let cmd = format!(
-            "ont add {port} sn-auth {sn} omci ont-lineprofile-id {line_profile_id} ont-srvprofile-id {service_profile_id} desc {description}"
+            "ont add {target} sn-auth {sn} omci ont-lineprofile-id {line_profile_id} ont-srvprofile-id {service_profile_id} desc {description}"
         );
 
-        let prompt = format!("(config-if-gpon-{frame}/{slot})#");
         let output = self.execute_command(&cmd, &prompt)?;
 
         check_for_failure(&output)?;
 
-        extract_ont_id(&output)
-            .ok_or_else(|| Error::ParseError("Failed to extract ONT ID from output".to_string()))
+        Ok(extract_ont_id(&output))
     }
 
     pub fn ont_delete_all(&mut self, port: u32) -> Result<()> {
-        if self.context.level != SessionLevel::InterfaceGpon {
-            return Err(Error::InvalidContext(
-                "Must be in interface gpon mode".to_string(),
-            ));
-        }
-
-        let frame = self
-            .context
-            .frame
-            .ok_or_else(|| Error::InvalidContext("Frame not set in context".to_string()))?;
-        let slot = self
-            .context
-            .slot
-            .ok_or_else(|| Error::InvalidContext("Slot not set in context".to_string()))?;
+        let prompt = self.interface_gpon_prompt()?;
 
         let cmd = format!("ont delete {port} all");
-        let prompt = format!("(config-if-gpon-{frame}/{slot})#");
         let output = self.execute_command(&cmd, &prompt)?;
         check_for_failure(&output)?;
         Ok(())
     }
 
+    pub fn ont_delete(&mut self, port: u32, ont_id: u32) -> Result<()> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("ont delete {port} {ont_id}");
+        let output = self.execute_command(&cmd, &prompt)?;
+        check_for_failure(&output)?;
+        Ok(())
+    }
+
+    /// Modifies the description and/or profiles of an existing ONT.
+    ///
+    /// Fields passed as `None` are left untouched on the device.
+    pub fn ont_modify(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        description: Option<&str>,
+        line_profile_id: Option<u32>,
+        service_profile_id: Option<u32>,
+    ) -> Result<()> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        if line_profile_id.is_some() || service_profile_id.is_some() {
+            let mut parts = vec![format!("ont modify {port} {ont_id}")];
+            if let Some(id) = line_profile_id {
+                parts.push(format!("ont-lineprofile-id {id}"));
+            }
+            if let Some(id) = service_profile_id {
+                parts.push(format!("ont-srvprofile-id {id}"));
+            }
+            let cmd = parts.join(" ");
+
+            let output = self.execute_command(&cmd, &prompt)?;
+            check_for_failure(&output)?;
+        }
+
+        if let Some(description) = description {
+            let cmd = format!("ont modify {port} {ont_id} desc {description}");
+            let output = self.execute_command(&cmd, &prompt)?;
+            check_for_failure(&output)?;
+        }
+
+        Ok(())
+    }
+
     pub fn ont_port_native_vlan(
         &mut self,
         port: u32,
         ont_id: u32,
-        ont_type: &str,
+        port_type: OntPortType,
+        ont_port: u32,
         vlan: u32,
         priority: u32,
     ) -> Result<()> {
-        if self.context.level != SessionLevel::InterfaceGpon {
-            return Err(Error::InvalidContext(
-                "Must be in interface gpon mode".to_string(),
-            ));
-        }
-
-        let frame = self
-            .context
-            .frame
-            .ok_or_else(|| Error::InvalidContext("Frame not set in context".to_string()))?;
-        let slot = self
-            .context
-            .slot
-            .ok_or_else(|| Error::InvalidContext("Slot not set in context".to_string()))?;
+        let prompt = self.interface_gpon_prompt()?;
 
         let cmd = format!(
-            "ont port native-vlan {port} {ont_id} {ont_type} vlan {vlan} priority {priority}"
+            "ont port native-vlan {port} {ont_id} {port_type} {ont_port} vlan {vlan} priority {priority}"
         );
 
-        let prompt = format!("(config-if-gpon-{frame}/{slot})#");
         let output = self.execute_command(&cmd, &prompt)?;
 
         check_for_failure(&output)?;
         Ok(())
     }
 
-    pub fn service_port_add(&mut self, config: &ServicePortConfig) -> Result<()> {
-        if self.context.level != SessionLevel::Config {
-            return Err(Error::InvalidContext("Must be in config mode".to_string()));
-        }
-
-        let cmd = format!(
-            "service-port vlan {} gpon {}/{}/{} ont {} gemport {} multi-service user-vlan {} tag-transform translate inbound traffic-table index {} outbound traffic-table index {}",
-            config.vlan, config.fsp.frame, config.fsp.slot, config.fsp.port, config.ont_id, config.gemport, config.user_vlan, config.inbound_traffic_table, config.outbound_traffic_table
-        );
+    /// Sets the same native VLAN on several ONT ports, e.g. `1..=4` or `[1, 3]`.
+    ///
+    /// Stops at the first port that fails.
+    pub fn ont_ports_native_vlan<I>(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+        ont_ports: I,
+        vlan: u32,
+        priority: u32,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        for ont_port in ont_ports {
+            self.ont_port_native_vlan(port, ont_id, port_type, ont_port, vlan, priority)?;
+        }
+        Ok(())
+    }
+
+    pub fn display_ont_port_attribute(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+    ) -> Result<Vec<OntPortAttribute>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display ont port attribute {port} {ont_id} {port_type}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_port_attributes(&output))
+    }
+
+    /// Runs `display ont register-info <port> <ont_id>` and returns the registration history.
+    pub fn display_ont_register_info(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+    ) -> Result<Vec<OntRegisterRecord>> {
+        let prompt = self.interface_gpon_prompt()?;
+
+        let cmd = format!("display ont register-info {port} {ont_id}");
+        let output = self.execute_command(&cmd, &prompt)?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_register_info(&output))
+    }
+
+    /// Runs `display ont port state <F/S/P> <ont_id> eth-port all`.
+    pub fn display_ont_eth_port_state(
+        &mut self,
+        fsp: Fsp,
+        ont_id: u32,
+    ) -> Result<Vec<OntEthPortState>> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
 
+        let cmd = format!("display ont port state {fsp} {ont_id} eth-port all");
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        check_for_failure(&output)?;
+        Ok(parse_ont_eth_port_states(&output))
+    }
+
+    /// Adds a service port after checking that its VLAN exists and has an uplink.
+    pub fn service_port_add(&mut self, config: &ServicePortConfig) -> Result<()> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+        self.ensure_vlan_exists(config.vlan)?;
+        self.service_port_add_unchecked(config)
+    }
+
+    /// Adds a service port without looking up its VLAN first, for batches whose
+    /// VLANs were checked once with [`Connection::check_service_port_vlans`].
+    pub fn service_port_add_unchecked(&mut self, config: &ServicePortConfig) -> Result<()> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("service-port {}", service_port_arguments(config));
         let output = self.execute_command(&cmd, "(config)#")?;
         check_for_failure(&output)?;
         Ok(())
     }
 
+    /// Adds a service port at an explicitly chosen index instead of the next free one.
+    pub fn service_port_add_with_index(
+        &mut self,
+        index: u32,
+        config: &ServicePortConfig,
+    ) -> Result<()> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+        self.ensure_vlan_exists(config.vlan)?;
+        self.service_port_add_with_index_unchecked(index, config)
+    }
+
+    /// Like [`Connection::service_port_add_with_index`] without the VLAN lookup.
+    pub fn service_port_add_with_index_unchecked(
+        &mut self,
+        index: u32,
+        config: &ServicePortConfig,
+    ) -> Result<()> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("service-port {index} {}", service_port_arguments(config));
+        let output = self.execute_command(&cmd, "(config)#")?;
+        check_for_failure(&output)?;
+        Ok(())
+    }
+
+    /// Returns the index the OLT would assign to the next service port.
+    pub fn display_service_port_next_free_index(&mut self) -> Result<u32> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let output = self.execute_command("display service-port next-free-index", "(config)#")?;
+        check_for_failure(&output)?;
+
+        extract_next_free_service_port_index(&output).ok_or_else(|| {
+            Error::ParseError("Failed to extract next free service-port index".to_string())
+        })
+    }
+
     pub fn service_port_undo(&mut self, service_port_id: u32) -> Result<()> {
         if self.context.level != SessionLevel::Config {
             return Err(Error::InvalidContext("Must be in config mode".to_string()));
@@ -449,38 +939,81 @@ impl Connection {
             SessionLevel::Enable => "#",
             SessionLevel::Config => "(config)#",
             SessionLevel::InterfaceGpon => {
-                let frame = self
-                    .context
-                    .frame
-                    .ok_or_else(|| Error::InvalidContext("Frame not set in context".to_string()))?;
-                let slot = self
-                    .context
-                    .slot
-                    .ok_or_else(|| Error::InvalidContext("Slot not set in context".to_string()))?;
-                return self.execute_command(command, &format!("(config-if-gpon-{frame}/{slot})#"));
+                let prompt = self.interface_gpon_prompt()?;
+                return self.execute_command(command, &prompt);
+            }
+            SessionLevel::GponLineProfile | SessionLevel::GponServiceProfile => {
+                let prompt = self.profile_prompt(self.context.level)?;
+                return self.execute_command(command, &prompt);
             }
         };
 
         self.execute_command(command, prompt)
     }
 
+    fn profile_prompt(&self, level: SessionLevel) -> Result<String> {
+        let kind = level
+            .profile_kind()
+            .ok_or_else(|| Error::InvalidContext("Not a profile level".to_string()))?;
+
+        if self.context.level != level {
+            return Err(Error::InvalidContext(format!(
+                "Must be in gpon {kind} mode"
+            )));
+        }
+
+        let id = self
+            .context
+            .profile_id
+            .ok_or_else(|| Error::InvalidContext("Profile not set in context".to_string()))?;
+
+        Ok(format!("(config-gpon-{kind}-{id})#"))
+    }
+
+    fn interface_gpon_prompt(&self) -> Result<String> {
+        if self.context.level != SessionLevel::InterfaceGpon {
+            return Err(Error::InvalidContext(
+                "Must be in interface gpon mode".to_string(),
+            ));
+        }
+
+        let frame = self
+            .context
+            .frame
+            .ok_or_else(|| Error::InvalidContext("Frame not set in context".to_string()))?;
+        let slot = self
+            .context
+            .slot
+            .ok_or_else(|| Error::InvalidContext("Slot not set in context".to_string()))?;
+
+        Ok(format!("(config-if-gpon-{frame}/{slot})#"))
+    }
+
     fn execute_command(&mut self, command: &str, expected_prompt: &str) -> Result<String> {
+        self.execute_command_until_any(command, &[expected_prompt])
+    }
+
+    /// Runs `command` and reads until any of `prompts`, for commands that only
+    /// change the prompt when they succeed.
+    fn execute_command_until_any(&mut self, command: &str, prompts: &[&str]) -> Result<String> {
         self.drain_channel_available(None)?;
         self.channel.write_all(command.as_bytes())?;
         self.channel.write_all(b"\n")?;
         self.channel.flush()?;
 
-        let output = self.read_until_prompt(expected_prompt)?;
+        let output = self.read_until_any_prompt(prompts)?;
         if output.contains("Failure: System is busy") {
-            return Err(Error::CommandFailed(
-                "System is busy, please retry after a while".to_string(),
-            ));
+            return Err(Error::CommandFailed(SYSTEM_BUSY.to_string()));
         }
 
         Ok(output)
     }
 
     fn read_until_prompt(&mut self, prompt: &str) -> Result<String> {
+        self.read_until_any_prompt(&[prompt])
+    }
+
+    fn read_until_any_prompt(&mut self, prompts: &[&str]) -> Result<String> {
         let mut buffer = vec![0; 4096];
         let mut output = String::new();
         let mut sent_more = false;
@@ -505,7 +1038,8 @@ impl Connection {
                         self.channel.flush()?;
                     }
 
-                    if output.trim_end().ends_with(prompt) {
+                    let trimmed = output.trim_end();
+                    if prompts.iter().any(|prompt| trimmed.ends_with(prompt)) {
                         break;
                     }
                 }
@@ -543,6 +1077,525 @@ impl Connection {
         Ok(())
     }
 
+    /// Runs `display board <frame>` and returns the shelf overview.
+    pub fn display_board(&mut self, frame: u32) -> Result<Vec<BoardSlot>> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("display board {frame}");
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        check_for_failure(&output)?;
+        Ok(parse_display_board(&output))
+    }
+
+    /// Runs `display board <frame>/<slot>` and returns the board detail.
+    pub fn display_board_detail(&mut self, frame: u32, slot: u32) -> Result<Option<BoardDetail>> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("display board {frame}/{slot}");
+        let output = self.execute_command(&cmd, "(config)#")?;
+
+        check_for_failure(&output)?;
+        Ok(parse_board_detail(&output))
+    }
+
+    /// Runs `display ont-lineprofile gpon all`.
+    pub fn display_ont_line_profiles(&mut self) -> Result<Vec<ProfileSummary>> {
+        let output = self.display_config("display ont-lineprofile gpon all")?;
+        Ok(parse_profile_summaries(&output))
+    }
+
+    /// Runs `display ont-lineprofile gpon profile-id <id>`.
+    pub fn display_ont_line_profile(&mut self, id: u32) -> Result<Option<LineProfile>> {
+        let output =
+            self.display_config(&format!("display ont-lineprofile gpon profile-id {id}"))?;
+        Ok(parse_line_profile(&output))
+    }
+
+    /// Runs `display ont-srvprofile gpon all`.
+    pub fn display_ont_service_profiles(&mut self) -> Result<Vec<ProfileSummary>> {
+        let output = self.display_config("display ont-srvprofile gpon all")?;
+        Ok(parse_profile_summaries(&output))
+    }
+
+    /// Runs `display ont-srvprofile gpon profile-id <id>`.
+    pub fn display_ont_service_profile(&mut self, id: u32) -> Result<Option<ServiceProfile>> {
+        let output =
+            self.display_config(&format!("display ont-srvprofile gpon profile-id {id}"))?;
+        Ok(parse_service_profile(&output))
+    }
+
+    /// Runs `display dba-profile all`.
+    pub fn display_dba_profiles(&mut self) -> Result<Vec<DbaProfile>> {
+        let output = self.display_config("display dba-profile all")?;
+        Ok(parse_dba_profiles(&output))
+    }
+
+    /// Runs `dba-profile add` with an explicit profile ID.
+    pub fn dba_profile_add(&mut self, id: u32, name: &str, bandwidth: DbaBandwidth) -> Result<()> {
+        self.config_command(&dba_profile_add_command(id, name, bandwidth)?)
+    }
+
+    /// Runs `dba-profile modify` to change the bandwidth of an existing profile.
+    pub fn dba_profile_modify(&mut self, id: u32, bandwidth: DbaBandwidth) -> Result<()> {
+        self.config_command(&format!(
+            "dba-profile modify profile-id {id} {}",
+            bandwidth.arguments()
+        ))
+    }
+
+    pub fn dba_profile_delete(&mut self, id: u32) -> Result<()> {
+        self.config_command(&format!("undo dba-profile profile-id {id}"))
+    }
+
+    /// Enters `(config-gpon-lineprofile-<id>)#`, creating the profile if needed.
+    pub fn line_profile(&mut self, id: u32, name: Option<&str>) -> Result<()> {
+        self.enter_profile(SessionLevel::GponLineProfile, id, name)
+    }
+
+    /// Enters the line profile `id` unless the session is already there.
+    pub fn ensure_line_profile(&mut self, id: u32) -> Result<()> {
+        if self.context.level == SessionLevel::GponLineProfile
+            && self.context.profile_id == Some(id)
+        {
+            return Ok(());
+        }
+
+        self.ensure_config()?;
+        self.line_profile(id, None)
+    }
+
+    /// Binds a T-CONT to a DBA profile in the current line profile.
+    pub fn line_profile_tcont(&mut self, tcont_id: u32, dba_profile_id: u32) -> Result<()> {
+        self.profile_command(
+            SessionLevel::GponLineProfile,
+            &format!("tcont {tcont_id} dba-profile-id {dba_profile_id}"),
+        )
+    }
+
+    /// Adds an ETH GEM port bound to `tcont_id` in the current line profile.
+    pub fn line_profile_gem_add(
+        &mut self,
+        gem_index: u32,
+        tcont_id: u32,
+        encrypt: bool,
+    ) -> Result<()> {
+        let encrypt = if encrypt { "on" } else { "off" };
+        self.profile_command(
+            SessionLevel::GponLineProfile,
+            &format!("gem add {gem_index} eth tcont {tcont_id} encrypt {encrypt}"),
+        )
+    }
+
+    pub fn line_profile_gem_delete(&mut self, gem_index: u32) -> Result<()> {
+        self.profile_command(
+            SessionLevel::GponLineProfile,
+            &format!("gem delete {gem_index}"),
+        )
+    }
+
+    /// Maps `vlan` (optionally with an 802.1p priority) to a GEM port.
+    pub fn line_profile_gem_mapping(
+        &mut self,
+        gem_index: u32,
+        mapping_index: u32,
+        vlan: u32,
+        priority: Option<u32>,
+    ) -> Result<()> {
+        let mut cmd = format!("gem mapping {gem_index} {mapping_index} vlan {vlan}");
+        if let Some(priority) = priority {
+            cmd = format!("{cmd} priority {priority}");
+        }
+        self.profile_command(SessionLevel::GponLineProfile, &cmd)
+    }
+
+    /// Commits the current line profile so bound ONTs pick up the changes.
+    pub fn line_profile_commit(&mut self) -> Result<()> {
+        self.profile_command(SessionLevel::GponLineProfile, "commit")
+    }
+
+    pub fn line_profile_delete(&mut self, id: u32) -> Result<()> {
+        self.config_command(&format!("undo ont-lineprofile gpon profile-id {id}"))
+    }
+
+    /// Enters `(config-gpon-srvprofile-<id>)#`, creating the profile if needed.
+    pub fn service_profile(&mut self, id: u32, name: Option<&str>) -> Result<()> {
+        self.enter_profile(SessionLevel::GponServiceProfile, id, name)
+    }
+
+    /// Enters the service profile `id` unless the session is already there.
+    pub fn ensure_service_profile(&mut self, id: u32) -> Result<()> {
+        if self.context.level == SessionLevel::GponServiceProfile
+            && self.context.profile_id == Some(id)
+        {
+            return Ok(());
+        }
+
+        self.ensure_config()?;
+        self.service_profile(id, None)
+    }
+
+    /// Declares ONT port counts in the current service profile.
+    pub fn service_profile_ports(&mut self, ports: &[(OntPortType, PortCount)]) -> Result<()> {
+        let arguments: Vec<String> = ports
+            .iter()
+            .map(|(port_type, count)| format!("{port_type} {count}"))
+            .collect();
+        self.profile_command(
+            SessionLevel::GponServiceProfile,
+            &format!("ont-port {}", arguments.join(" ")),
+        )
+    }
+
+    pub fn service_profile_native_vlan(
+        &mut self,
+        port_type: OntPortType,
+        ont_port: u32,
+        vlan: u32,
+        priority: u32,
+    ) -> Result<()> {
+        self.profile_command(
+            SessionLevel::GponServiceProfile,
+            &format!("port native-vlan {port_type} {ont_port} vlan {vlan} priority {priority}"),
+        )
+    }
+
+    /// Commits the current service profile so bound ONTs pick up the changes.
+    pub fn service_profile_commit(&mut self) -> Result<()> {
+        self.profile_command(SessionLevel::GponServiceProfile, "commit")
+    }
+
+    pub fn service_profile_delete(&mut self, id: u32) -> Result<()> {
+        self.config_command(&format!("undo ont-srvprofile gpon profile-id {id}"))
+    }
+
+    /// Enters profile `id`; the prompt only changes when the OLT accepts it.
+    fn enter_profile(&mut self, level: SessionLevel, id: u32, name: Option<&str>) -> Result<()> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext(
+                "Not in config mode, cannot enter profile".to_string(),
+            ));
+        }
+
+        let cmd = level.profile_enter_command(id, name)?;
+        let prompt = level.profile_prompt(id).unwrap_or_default();
+
+        let output = self.execute_command_until_any(&cmd, &[&prompt, "(config)#"])?;
+        check_for_failure(&output)?;
+        if !output.trim_end().ends_with(&prompt) {
+            return Err(Error::CommandFailed(format!("OLT did not enter {prompt}")));
+        }
+
+        self.context.level = level;
+        self.context.profile_id = Some(id);
+
+        Ok(())
+    }
+
+    fn profile_command(&mut self, level: SessionLevel, cmd: &str) -> Result<()> {
+        let prompt = self.profile_prompt(level)?;
+        let output = self.execute_command(cmd, &prompt)?;
+
+        check_for_failure(&output)
+    }
+
+    /// Runs `display traffic table ip from-index 0`.
+    pub fn display_traffic_tables(&mut self) -> Result<Vec<TrafficTable>> {
+        let output = self.display_config("display traffic table ip from-index 0")?;
+        Ok(parse_traffic_tables(&output))
+    }
+
+    pub fn traffic_table_add(&mut self, index: u32, config: &TrafficTableConfig) -> Result<()> {
+        self.config_command(&format!(
+            "traffic table ip index {index} {}",
+            config.arguments()?
+        ))
+    }
+
+    pub fn traffic_table_delete(&mut self, index: u32) -> Result<()> {
+        self.config_command(&format!("undo traffic table ip index {index}"))
+    }
+
+    /// Returns the index of a traffic table matching `config`, adding one
+    /// at the first free index when none exists.
+    pub fn ensure_traffic_table(&mut self, config: &TrafficTableConfig) -> Result<u32> {
+        let tables = self.display_traffic_tables()?;
+        if let Some(index) = find_traffic_table(&tables, config) {
+            return Ok(index);
+        }
+
+        let index = free_traffic_table_index(&tables);
+        self.traffic_table_add(index, config)?;
+        Ok(index)
+    }
+
+    /// Runs `display vlan all`.
+    pub fn display_vlan_all(&mut self) -> Result<Vec<Vlan>> {
+        let output = self.display_config("display vlan all")?;
+        Ok(parse_vlans(&output))
+    }
+
+    /// Runs `display vlan <id>`, returning `None` if the VLAN does not exist.
+    pub fn display_vlan(&mut self, id: u32) -> Result<Option<VlanDetail>> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = format!("display vlan {id}");
+        let output = self.execute_command(&cmd, "(config)#")?;
+        if output.contains("does not exist") {
+            return Ok(None);
+        }
+
+        check_for_failure(&output)?;
+        Ok(parse_vlan_detail(&output))
+    }
+
+    pub fn vlan_add(&mut self, id: u32, vlan_type: VlanType) -> Result<()> {
+        self.config_command(&format!("vlan {id} {}", vlan_type.as_str()))
+    }
+
+    pub fn vlan_delete(&mut self, id: u32) -> Result<()> {
+        self.config_command(&format!("undo vlan {id}"))
+    }
+
+    /// Binds a VLAN to an uplink port with `port vlan`.
+    pub fn port_vlan_add(&mut self, id: u32, fsp: Fsp) -> Result<()> {
+        self.config_command(&format!(
+            "port vlan {id} {}/{} {}",
+            fsp.frame, fsp.slot, fsp.port
+        ))
+    }
+
+    pub fn port_vlan_delete(&mut self, id: u32, fsp: Fsp) -> Result<()> {
+        self.config_command(&format!(
+            "undo port vlan {id} {}/{} {}",
+            fsp.frame, fsp.slot, fsp.port
+        ))
+    }
+
+    /// Checks every VLAN of a batch of service ports with one `display vlan all`.
+    pub fn check_service_port_vlans(&mut self, vlans: &[u32]) -> Result<()> {
+        if vlans.is_empty() {
+            return Ok(());
+        }
+        let all = self.display_vlan_all()?;
+        check_service_port_vlans(&all, vlans)
+    }
+
+    fn ensure_vlan_exists(&mut self, vlan: i32) -> Result<()> {
+        let id = u32::try_from(vlan)
+            .map_err(|_| Error::CommandFailed(format!("Invalid VLAN {vlan}")))?;
+        match self.display_vlan(id)? {
+            Some(detail) if detail.has_uplink() => Ok(()),
+            Some(_) => Err(Error::VlanWithoutUplink(id)),
+            None => Err(Error::VlanNotFound(id)),
+        }
+    }
+
+    /// Runs `display version`.
+    pub fn display_version(&mut self) -> Result<SystemVersion> {
+        let output = self.display_config("display version")?;
+        Ok(parse_system_version(&output))
+    }
+
+    /// Runs `display sysuptime` and returns the uptime in seconds.
+    pub fn display_sysuptime(&mut self) -> Result<Option<u64>> {
+        let output = self.display_config("display sysuptime")?;
+        Ok(parse_uptime(&output))
+    }
+
+    /// Runs `display patch`.
+    pub fn display_patch(&mut self) -> Result<Vec<SystemPatch>> {
+        let output = self.display_config("display patch")?;
+        Ok(parse_system_patches(&output))
+    }
+
+    /// Runs `display cpu <frame>/<slot>` and returns the CPU occupancy in percent.
+    pub fn display_cpu(&mut self, frame: u32, slot: u32) -> Result<Option<u32>> {
+        let output = self.display_config(&format!("display cpu {frame}/{slot}"))?;
+        Ok(parse_occupancy(&output))
+    }
+
+    /// Runs `display memory <frame>/<slot>` and returns the memory occupancy in percent.
+    pub fn display_memory(&mut self, frame: u32, slot: u32) -> Result<Option<u32>> {
+        let output = self.display_config(&format!("display memory {frame}/{slot}"))?;
+        Ok(parse_occupancy(&output))
+    }
+
+    /// Runs `display temperature <frame>/<slot>` and returns the temperature in Celsius.
+    pub fn display_temperature(&mut self, frame: u32, slot: u32) -> Result<Option<i32>> {
+        let output = self.display_config(&format!("display temperature {frame}/{slot}"))?;
+        Ok(parse_temperature(&output))
+    }
+
+    /// Collects CPU, memory and temperature of one board.
+    ///
+    /// A metric the board rejects is reported as `None` instead of failing the
+    /// whole call; session errors are still returned.
+    pub fn display_board_health(&mut self, frame: u32, slot: u32) -> Result<BoardHealth> {
+        Ok(BoardHealth {
+            frame,
+            slot,
+            board_name: None,
+            cpu_percent: board_metric(self.display_cpu(frame, slot))?,
+            memory_percent: board_metric(self.display_memory(frame, slot))?,
+            temperature: board_metric(self.display_temperature(frame, slot))?,
+        })
+    }
+
+    /// Collects version, uptime, patches and the health of every working board
+    /// in `frame`.
+    pub fn display_system_info(&mut self, frame: u32) -> Result<SystemInfo> {
+        let version = self.display_version()?;
+        let uptime_seconds = match version.uptime_seconds {
+            Some(seconds) => Some(seconds),
+            None => self.display_sysuptime()?,
+        };
+        let patches = self.display_patch()?;
+
+        let mut boards = Vec::new();
+        for board in self.display_board(frame)? {
+            let working = board
+                .status
+                .as_deref()
+                .is_some_and(|status| status.to_ascii_lowercase().contains("normal"));
+            if !working {
+                continue;
+            }
+            let mut health = self.display_board_health(frame, board.slot_id)?;
+            health.board_name = board.board_name;
+            boards.push(health);
+        }
+
+        Ok(SystemInfo {
+            version,
+            uptime_seconds,
+            patches,
+            boards,
+        })
+    }
+
+    /// Runs `save` and waits until the OLT reports the configuration as saved.
+    pub fn save(&mut self) -> Result<()> {
+        self.save_with_timeout(SAVE_TIMEOUT)
+    }
+
+    pub fn save_with_timeout(&mut self, timeout: Duration) -> Result<()> {
+        let mut output = self.display_config("save")?;
+        let start = Instant::now();
+
+        loop {
+            match save_status(&output) {
+                SaveStatus::Succeeded => return Ok(()),
+                SaveStatus::Failed => {
+                    return Err(Error::CommandFailed(
+                        "Saving the configuration failed".to_string(),
+                    ))
+                }
+                SaveStatus::InProgress => {}
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(Error::CommandFailed(
+                    "Timed out waiting for the configuration to be saved".to_string(),
+                ));
+            }
+            sleep(Duration::from_secs(1));
+            self.drain_channel_available(Some(&mut output))?;
+        }
+    }
+
+    /// Returns `display current-configuration`, or only `section` of it.
+    pub fn display_current_configuration(&mut self, section: Option<&str>) -> Result<String> {
+        let mut config = Vec::new();
+        self.write_current_configuration(section, &mut config)?;
+        Ok(String::from_utf8_lossy(&config).into_owned())
+    }
+
+    /// Fetches `display current-configuration` and parses it into a typed model.
+    pub fn display_running_config(&mut self) -> Result<RunningConfig> {
+        let config = self.display_current_configuration(None)?;
+        Ok(parse_running_config(&config))
+    }
+
+    /// Streams `display current-configuration` (or only `section` of it) into
+    /// `writer` line by line, answering every pager prompt.
+    pub fn write_current_configuration<W: Write>(
+        &mut self,
+        section: Option<&str>,
+        writer: &mut W,
+    ) -> Result<()> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let cmd = section.map_or_else(
+            || "display current-configuration".to_string(),
+            |section| format!("display current-configuration section {section}"),
+        );
+        self.drain_channel_available(None)?;
+        self.channel.write_all(cmd.as_bytes())?;
+        self.channel.write_all(b"\n")?;
+        self.channel.flush()?;
+
+        let mut buffer = vec![0; 4096];
+        let mut filter = ConfigurationFilter::new();
+        let mut sent_cr_prompt = false;
+
+        loop {
+            let n = self.channel.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+
+            for line in filter.push(&String::from_utf8_lossy(&buffer[..n])) {
+                writeln!(writer, "{line}")?;
+            }
+            if let Some(msg) = filter.failure() {
+                return Err(Error::CommandFailed(msg.to_string()));
+            }
+
+            if filter.take_pager_prompt() {
+                self.channel.write_all(b"\n")?;
+                self.channel.flush()?;
+            }
+
+            if !sent_cr_prompt && filter.pending().contains(" }:") {
+                sent_cr_prompt = true;
+                self.channel.write_all(b"\n")?;
+                self.channel.flush()?;
+            }
+
+            if filter.pending().trim_end().ends_with("(config)#") {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs a config-mode command and checks it for failures.
+    fn config_command(&mut self, cmd: &str) -> Result<()> {
+        self.display_config(cmd).map(|_| ())
+    }
+
+    /// Runs a config-mode display command and checks it for failures.
+    fn display_config(&mut self, cmd: &str) -> Result<String> {
+        if self.context.level != SessionLevel::Config {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+
+        let output = self.execute_command(cmd, "(config)#")?;
+
+        check_for_failure(&output)?;
+        Ok(output)
+    }
+
     pub fn display_alarm_active_all_list(&mut self) -> Result<ActiveAlarms> {
         if self.context.level != SessionLevel::Config {
             return Err(Error::InvalidContext("Must be in config mode".to_string()));
@@ -561,3 +1614,43 @@ impl Connection {
         Ok(parse_active_alarms_detail(&output))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn board_metric_maps_board_rejections_to_none() {
+        let rejected = check_for_failure("  Failure: The board does not support this operation");
+        assert!(matches!(
+            board_metric::<u32>(rejected.map(|()| None)),
+            Ok(None)
+        ));
+
+        let parameter_error = check_for_failure("  % Parameter error, the error locates at '^'");
+        assert!(matches!(
+            board_metric::<u32>(parameter_error.map(|()| None)),
+            Ok(None)
+        ));
+
+        assert!(matches!(board_metric(Ok(Some(12))), Ok(Some(12))));
+    }
+
+    #[test]
+    fn board_metric_returns_busy_and_session_errors() {
+        let busy = Err(Error::CommandFailed(SYSTEM_BUSY.to_string()));
+        assert!(matches!(
+            board_metric::<u32>(busy),
+            Err(Error::CommandFailed(msg)) if msg == SYSTEM_BUSY
+        ));
+
+        let busy_line = check_for_failure("  Failure: System is busy");
+        assert!(board_metric::<u32>(busy_line.map(|()| None)).is_err());
+
+        let context = Err(Error::InvalidContext("Must be in config mode".to_string()));
+        assert!(matches!(
+            board_metric::<u32>(context),
+            Err(Error::InvalidContext(_))
+        ));
+    }
+}
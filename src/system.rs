@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Device facts gathered from `display version`, `display sysuptime`,
+/// `display patch` and the per-board health queries
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct SystemInfo {
+    pub version: SystemVersion,
+    /// System uptime in seconds
+    pub uptime_seconds: Option<u64>,
+    pub patches: Vec<SystemPatch>,
+    pub boards: Vec<BoardHealth>,
+}
+
+/// Output of `display version`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct SystemVersion {
+    /// Software version (e.g. `MA5800V100R019C10`)
+    pub version: String,
+    /// Installed patches as printed on the `PATCH` line
+    pub patch: Option<String>,
+    /// Product name (e.g. `MA5800-X7`)
+    pub product: String,
+    pub uptime_seconds: Option<u64>,
+}
+
+/// Row of `display patch`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct SystemPatch {
+    pub name: String,
+    /// Patch type (e.g. `C` for cold, `HP` for hot)
+    pub patch_type: String,
+    /// Patch state (e.g. `Running`, `Active`)
+    pub state: String,
+    pub time: Option<String>,
+}
+
+/// CPU, memory and temperature of one board
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct BoardHealth {
+    pub frame: u32,
+    pub slot: u32,
+    pub board_name: Option<String>,
+    /// CPU occupancy in percent
+    pub cpu_percent: Option<u32>,
+    /// Memory occupancy in percent
+    pub memory_percent: Option<u32>,
+    /// Board temperature in Celsius
+    pub temperature: Option<i32>,
+}
+
+#[must_use]
+pub fn parse_system_version(output: &str) -> SystemVersion {
+    let mut version = SystemVersion {
+        uptime_seconds: parse_uptime(output),
+        ..SystemVersion::default()
+    };
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "VERSION" => version.version = value.to_string(),
+            "PATCH" => version.patch = (!value.is_empty()).then(|| value.to_string()),
+            "PRODUCT" => version.product = value.to_string(),
+            _ => {}
+        }
+    }
+
+    version
+}
+
+/// Parses an uptime such as `125 day(s), 3 hour(s), 27 minute(s), 10 second(s)`
+/// from `display version` or `display sysuptime` into seconds.
+#[must_use]
+pub fn parse_uptime(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .filter(|line| {
+            let line = line.to_ascii_lowercase();
+            line.contains("up time") || line.contains("uptime")
+        })
+        .find_map(uptime_seconds)
+}
+
+fn uptime_seconds(line: &str) -> Option<u64> {
+    let words: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ':')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut seconds = None;
+    for pair in words.windows(2) {
+        let Ok(value) = pair[0].parse::<u64>() else {
+            continue;
+        };
+        let unit = pair[1].to_ascii_lowercase();
+        let multiplier = if unit.starts_with("day") {
+            86_400
+        } else if unit.starts_with("hour") {
+            3_600
+        } else if unit.starts_with("min") {
+            60
+        } else if unit.starts_with("sec") {
+            1
+        } else {
+            continue;
+        };
+        seconds = Some(seconds.unwrap_or(0) + value * multiplier);
+    }
+
+    seconds
+}
+
+#[must_use]
+pub fn parse_system_patches(output: &str) -> Vec<SystemPatch> {
+    let mut patches = Vec::new();
+    let mut in_table = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Patch Name") {
+            in_table = true;
+            continue;
+        }
+        if !in_table || trimmed.is_empty() || trimmed.starts_with('-') {
+            continue;
+        }
+        if trimmed.starts_with("Total") {
+            break;
+        }
+
+        // Example: "SPC200      C           Running      2023-04-12 10:22:31"
+        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        patches.push(SystemPatch {
+            name: parts[0].to_string(),
+            patch_type: parts[1].to_string(),
+            state: parts[2].to_string(),
+            time: (parts.len() > 3).then(|| parts[3..].join(" ")),
+        });
+    }
+
+    patches
+}
+
+/// Parses the occupancy percentage from `display cpu` or `display memory`.
+#[must_use]
+pub fn parse_occupancy(output: &str) -> Option<u32> {
+    // Example: "CPU occupancy: 12%"
+    output
+        .lines()
+        .find(|line| line.to_ascii_lowercase().contains("occupancy"))
+        .and_then(|line| line.rsplit_once(':'))
+        .and_then(|(_, value)| value.trim().trim_end_matches('%').trim().parse().ok())
+}
+
+/// Parses the board temperature in Celsius from `display temperature`.
+#[must_use]
+pub fn parse_temperature(output: &str) -> Option<i32> {
+    // Example: "The temperature of the board: 47C"
+    output
+        .lines()
+        .find(|line| line.to_ascii_lowercase().contains("temperature of"))
+        .and_then(|line| line.rsplit_once(':'))
+        .and_then(|(_, value)| {
+            value
+                .trim()
+                .trim_end_matches(|c: char| !c.is_ascii_digit())
+                .parse()
+                .ok()
+        })
+}
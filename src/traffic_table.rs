@@ -0,0 +1,144 @@
+//! IP traffic tables referenced by service ports.
+//!
+//! Service ports take inbound/outbound traffic-table indexes; these helpers list
+//! the tables on the OLT and resolve a table configuration to an index.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::Result;
+use crate::parser::quote_argument;
+
+/// Highest index of the OLT's built-in traffic tables (0-6).
+pub const DEFAULT_TRAFFIC_TABLE_MAX_INDEX: u32 = 6;
+
+/// Row of `display traffic table ip`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct TrafficTable {
+    pub index: u32,
+    /// Committed information rate in kbit/s, `None` when `off`
+    pub cir_kbps: Option<u32>,
+    pub cbs_bytes: Option<u32>,
+    /// Peak information rate in kbit/s, `None` when `off`
+    pub pir_kbps: Option<u32>,
+    pub pbs_bytes: Option<u32>,
+    /// 802.1p priority, `None` when not set
+    pub priority: Option<u32>,
+    pub copy_policy: Option<String>,
+    /// Priority policy as printed by the OLT (e.g. `tag-pri`, `local-pri`)
+    pub priority_policy: String,
+}
+
+/// Priority policy of a new traffic table
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityPolicy {
+    /// Use the priority configured in the table
+    #[default]
+    LocalSetting,
+    /// Keep the priority carried in the packet
+    TagInPackage,
+}
+
+impl PriorityPolicy {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::LocalSetting => "local-setting",
+            Self::TagInPackage => "tag-In-Package",
+        }
+    }
+
+    /// Parses the policy as printed by `display traffic table ip` or as configured.
+    #[must_use]
+    pub fn parse(policy: &str) -> Option<Self> {
+        match policy.to_ascii_lowercase().as_str() {
+            "local-pri" | "local-setting" => Some(Self::LocalSetting),
+            "tag-pri" | "tag-in-package" => Some(Self::TagInPackage),
+            _ => None,
+        }
+    }
+}
+
+/// Parameters of `traffic table ip index <index> ...`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct TrafficTableConfig {
+    pub name: String,
+    pub cir_kbps: u32,
+    /// Defaults to the CIR when `None`
+    pub pir_kbps: Option<u32>,
+    pub priority: u32,
+    pub priority_policy: PriorityPolicy,
+}
+
+impl TrafficTableConfig {
+    /// Returns the arguments following `traffic table ip index <index>`.
+    ///
+    /// Fails when the name cannot be quoted, see [`quote_argument`].
+    pub fn arguments(&self) -> Result<String> {
+        let mut arguments = format!("name {} cir {}", quote_argument(&self.name)?, self.cir_kbps);
+        if let Some(pir) = self.pir_kbps {
+            arguments = format!("{arguments} pir {pir}");
+        }
+        Ok(format!(
+            "{arguments} priority {} priority-policy {}",
+            self.priority,
+            self.priority_policy.as_str()
+        ))
+    }
+}
+
+/// Returns the index of the first table with the rates, priority and priority
+/// policy of `config`.
+///
+/// A `pir_kbps` of `None` matches tables whose PIR equals the CIR.
+#[must_use]
+pub fn find_traffic_table(tables: &[TrafficTable], config: &TrafficTableConfig) -> Option<u32> {
+    let pir_kbps = config.pir_kbps.unwrap_or(config.cir_kbps);
+    tables
+        .iter()
+        .find(|t| {
+            t.cir_kbps == Some(config.cir_kbps)
+                && t.pir_kbps == Some(pir_kbps)
+                && t.priority == Some(config.priority)
+                && PriorityPolicy::parse(&t.priority_policy) == Some(config.priority_policy)
+        })
+        .map(|t| t.index)
+}
+
+/// Returns the lowest index above the built-in tables that is not in use.
+#[must_use]
+pub fn free_traffic_table_index(tables: &[TrafficTable]) -> u32 {
+    (DEFAULT_TRAFFIC_TABLE_MAX_INDEX + 1..u32::MAX)
+        .find(|index| !tables.iter().any(|t| t.index == *index))
+        .unwrap_or(u32::MAX)
+}
+
+#[must_use]
+pub fn parse_traffic_tables(output: &str) -> Vec<TrafficTable> {
+    let mut tables = Vec::new();
+
+    for line in output.lines() {
+        // Example: "10   102400  3278848  204800   6555648     0    -              local-pri"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 8 {
+            continue;
+        }
+        let Ok(index) = parts[0].parse() else {
+            continue;
+        };
+
+        tables.push(TrafficTable {
+            index,
+            cir_kbps: parts[1].parse().ok(),
+            cbs_bytes: parts[2].parse().ok(),
+            pir_kbps: parts[3].parse().ok(),
+            pbs_bytes: parts[4].parse().ok(),
+            priority: parts[5].parse().ok(),
+            copy_policy: (parts[6] != "-").then(|| parts[6].to_string()),
+            priority_policy: parts[7].to_string(),
+        });
+    }
+
+    tables
+}
@@ -0,0 +1,180 @@
+use crate::error::{Error, Result};
+use crate::models::Fsp;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Row of `display vlan all`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct Vlan {
+    pub id: u32,
+    /// VLAN type (e.g. `smart`, `standard`, `mux`, `super`)
+    pub vlan_type: String,
+    /// VLAN attribute (e.g. `common`, `stacking`, `QinQ`)
+    pub attribute: String,
+    /// Number of standard (uplink) ports in the VLAN
+    pub standard_port_count: u32,
+    /// Number of service ports using the VLAN
+    pub service_port_count: u32,
+}
+
+impl Vlan {
+    /// Returns `true` if the VLAN is bound to at least one uplink port.
+    #[must_use]
+    pub const fn has_uplink(&self) -> bool {
+        self.standard_port_count > 0
+    }
+}
+
+/// Uplink port bound to a VLAN
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct VlanMemberPort {
+    pub fsp: Fsp,
+    pub native_vlan: u32,
+    /// Port state (e.g. `up`, `down`)
+    pub state: String,
+}
+
+/// VLAN detail from `display vlan <id>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default, PartialEq, Eq)]
+pub struct VlanDetail {
+    pub id: u32,
+    pub name: Option<String>,
+    pub vlan_type: String,
+    pub attribute: String,
+    pub description: Option<String>,
+    pub member_ports: Vec<VlanMemberPort>,
+    pub standard_port_count: u32,
+    pub service_port_count: u32,
+}
+
+impl VlanDetail {
+    /// Returns `true` if the VLAN is bound to at least one uplink port.
+    #[must_use]
+    pub const fn has_uplink(&self) -> bool {
+        !self.member_ports.is_empty()
+    }
+}
+
+/// Checks that every VLAN in `required` exists in `vlans` and has an uplink port.
+///
+/// Service ports on a VLAN without an uplink are accepted by the OLT but carry
+/// no traffic, so they are rejected like a missing VLAN.
+pub fn check_service_port_vlans(vlans: &[Vlan], required: &[u32]) -> Result<()> {
+    for &id in required {
+        match vlans.iter().find(|v| v.id == id) {
+            None => return Err(Error::VlanNotFound(id)),
+            Some(vlan) if !vlan.has_uplink() => return Err(Error::VlanWithoutUplink(id)),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Type of a VLAN created with `vlan <id> <type>`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VlanType {
+    #[default]
+    Smart,
+    Standard,
+    Mux,
+    Super,
+}
+
+impl VlanType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Smart => "smart",
+            Self::Standard => "standard",
+            Self::Mux => "mux",
+            Self::Super => "super",
+        }
+    }
+}
+
+#[must_use]
+pub fn parse_vlans(output: &str) -> Vec<Vlan> {
+    let mut vlans = Vec::new();
+
+    for line in output.lines() {
+        // Example: "1063   smart      common                 2            148  -"
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 {
+            continue;
+        }
+        let Ok(id) = parts[0].parse() else {
+            continue;
+        };
+        let (Ok(standard_port_count), Ok(service_port_count)) =
+            (parts[3].parse(), parts[4].parse())
+        else {
+            continue;
+        };
+
+        vlans.push(Vlan {
+            id,
+            vlan_type: parts[1].to_string(),
+            attribute: parts[2].to_string(),
+            standard_port_count,
+            service_port_count,
+        });
+    }
+
+    vlans
+}
+
+/// Parses `display vlan <id>`, returning `None` if the VLAN does not exist.
+#[must_use]
+pub fn parse_vlan_detail(output: &str) -> Option<VlanDetail> {
+    let member_re = Regex::new(r"^(\d+\s*/\s*\d+\s*/\s*\d+)\s+(\d+)\s+(\S+)$").ok()?;
+    let mut detail: Option<VlanDetail> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let value = value.trim();
+            let optional = || (!value.is_empty() && value != "-").then(|| value.to_string());
+
+            if key == "VLAN ID" {
+                detail = Some(VlanDetail {
+                    id: value.parse().ok()?,
+                    ..VlanDetail::default()
+                });
+                continue;
+            }
+            let Some(detail) = detail.as_mut() else {
+                continue;
+            };
+            match key {
+                "VLAN name" => detail.name = optional(),
+                "VLAN type" => detail.vlan_type = value.to_string(),
+                "VLAN attribute" => detail.attribute = value.to_string(),
+                "VLAN description" => detail.description = optional(),
+                "Standard port number" => detail.standard_port_count = value.parse().unwrap_or(0),
+                "Service virtual port number" => {
+                    detail.service_port_count = value.parse().unwrap_or(0);
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        // Example: "0/ 8/ 0            1  up"
+        let (Some(detail), Some(caps)) = (detail.as_mut(), member_re.captures(trimmed)) else {
+            continue;
+        };
+        let (Some(fsp), Ok(native_vlan)) = (Fsp::parse(&caps[1]), caps[2].parse()) else {
+            continue;
+        };
+        detail.member_ports.push(VlanMemberPort {
+            fsp,
+            native_vlan,
+            state: caps[3].to_string(),
+        });
+    }
+
+    detail
+}
@@ -0,0 +1,74 @@
+use oltcore::allocation::{free_ont_ids, free_service_port_indexes};
+use oltcore::{
+    parse_ont_info_summary, Fsp, OntIdAllocator, ServicePort, ServicePortIndexAllocator,
+};
+
+const PORT: Fsp = Fsp {
+    frame: 0,
+    slot: 5,
+    port: 0,
+};
+
+#[test]
+fn free_ont_ids_from_summary_fixture() {
+    let output = include_str!("fixtures/ont_info_summary.txt");
+    let summary = parse_ont_info_summary(output);
+    let port = summary
+        .ports
+        .iter()
+        .find(|p| p.fsp == PORT)
+        .expect("expected port 0/5/0");
+
+    let free = free_ont_ids(port);
+    assert!(!free.contains(&0));
+    assert_eq!(free.first(), Some(&63));
+    assert!(free.contains(&75));
+    assert_eq!(free.last(), Some(&127));
+    assert_eq!(free.len(), 128 - port.onts.len());
+}
+
+#[test]
+fn ont_id_allocator_reserves_and_releases() {
+    let output = include_str!("fixtures/ont_info_summary.txt");
+    let summary = parse_ont_info_summary(output);
+    let mut allocator = OntIdAllocator::from_summary(&summary);
+
+    assert_eq!(allocator.reserve(PORT), Some(63));
+    assert_eq!(allocator.reserve(PORT), Some(64));
+    allocator.release(PORT, 63);
+    assert_eq!(allocator.reserve(PORT), Some(63));
+
+    let empty = Fsp {
+        frame: 0,
+        slot: 7,
+        port: 0,
+    };
+    let ids: Vec<Option<u32>> = (0..129).map(|_| allocator.reserve(empty)).collect();
+    assert_eq!(ids[0], Some(0));
+    assert_eq!(ids[127], Some(127));
+    assert_eq!(ids[128], None);
+}
+
+#[test]
+fn free_service_port_indexes_skips_used() {
+    let ports = vec![
+        ServicePort {
+            index: 0,
+            vlan: 10,
+            ..ServicePort::default()
+        },
+        ServicePort {
+            index: 2,
+            vlan: 10,
+            ..ServicePort::default()
+        },
+    ];
+    assert_eq!(free_service_port_indexes(&ports, 0..=10, 3), vec![1, 3, 4]);
+
+    let mut allocator = ServicePortIndexAllocator::new(&ports, 0..=3);
+    assert_eq!(allocator.reserve(), Some(1));
+    assert_eq!(allocator.reserve(), Some(3));
+    assert_eq!(allocator.reserve(), None);
+    allocator.release(1);
+    assert_eq!(allocator.reserve(), Some(1));
+}
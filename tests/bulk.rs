@@ -0,0 +1,219 @@
+mod support;
+
+use oltcore::bulk::{parse_csv_manifest, parse_json_manifest, provision, validate_manifest};
+use oltcore::{BulkReport, Fsp, ManifestRow, OntPortType, RowResult, RowStatus};
+use support::FakeOlt;
+
+const CSV: &str = "sn,fsp,description,line_profile_id,service_profile_id,native_vlan,vlan,gemport,user_vlan,inbound_traffic_table,outbound_traffic_table\r
+HWTC-CB8FBDB4,0/9/2,JFTECH,60,35,20,1063,20,20,10,10\r
+\"4D4B5047B4BABE7C\",0/5/0,rozenilda.lins@gserv.net,60,35,,,,,,\r
+";
+
+#[test]
+fn parse_csv_manifest_rows() {
+    let manifest = parse_csv_manifest(CSV).expect("manifest should parse");
+    assert!(manifest.issues.is_empty());
+    let rows = manifest.rows;
+    assert_eq!(rows.len(), 2);
+
+    let first = &rows[0];
+    assert_eq!(first.row, 2);
+    assert_eq!(first.ont.sn, "HWTC-CB8FBDB4");
+    assert_eq!(
+        first.ont.fsp,
+        Fsp {
+            frame: 0,
+            slot: 9,
+            port: 2
+        }
+    );
+    assert_eq!(first.ont.native_vlans.len(), 1);
+    assert_eq!(first.ont.native_vlans[0].port_type, OntPortType::Eth);
+    assert_eq!(first.ont.native_vlans[0].ont_port, 1);
+    assert_eq!(first.ont.native_vlans[0].vlan, 20);
+    assert_eq!(first.ont.service_ports.len(), 1);
+    assert_eq!(first.ont.service_ports[0].vlan, 1063);
+    assert_eq!(first.ont.service_ports[0].gemport, 20);
+
+    let second = &rows[1];
+    assert_eq!(second.ont.sn, "4D4B5047B4BABE7C");
+    assert!(second.ont.native_vlans.is_empty());
+    assert!(second.ont.service_ports.is_empty());
+    assert!(validate_manifest(&rows).is_empty());
+}
+
+#[test]
+fn parse_csv_manifest_reports_bad_rows() {
+    let missing_column = "sn,fsp,description,line_profile_id\nHWTC-CB8FBDB4,0/9/2,x,1\n";
+    assert!(parse_csv_manifest(missing_column).is_err());
+    assert!(parse_csv_manifest("\n \n").is_err());
+
+    let csv = "sn,fsp,description,line_profile_id,service_profile_id,vlan\n\
+               HWTC-CB8FBDB4,0/9/2,x,ten,1,\n\
+               \n\
+               HWTC-00000001,0/9/2,ok,1,1,\n\
+               HWTC-00000002,0/9/2,x,1,1,100\n\
+               HWTC-00000003,0/9/99x,x,1,1,\n";
+    let manifest = parse_csv_manifest(csv).expect("bad rows should not fail the manifest");
+
+    let rows: Vec<usize> = manifest.rows.iter().map(|r| r.row).collect();
+    assert_eq!(rows, vec![4]);
+
+    let issues: Vec<(usize, &str)> = manifest
+        .issues
+        .iter()
+        .map(|i| (i.row, i.sn.as_str()))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            (2, "HWTC-CB8FBDB4"),
+            (5, "HWTC-00000002"),
+            (6, "HWTC-00000003")
+        ]
+    );
+    assert!(manifest.issues[0].message.contains("line_profile_id"));
+    assert!(manifest.issues[1].message.contains("gemport"));
+    assert!(manifest.issues[2].message.contains("F/S/P"));
+}
+
+#[test]
+fn parse_json_manifest_rows() {
+    let json = r#"[
+        {
+            "sn": "HWTC-CB8FBDB4",
+            "fsp": {"frame": 0, "slot": 9, "port": 2},
+            "description": "JFTECH",
+            "line_profile_id": 60,
+            "service_profile_id": 35,
+            "service_ports": [
+                {"vlan": 1063, "gemport": 20, "user_vlan": 20,
+                 "inbound_traffic_table": 10, "outbound_traffic_table": 10}
+            ]
+        }
+    ]"#;
+    let manifest = parse_json_manifest(json).expect("manifest should parse");
+    assert!(manifest.issues.is_empty());
+    let rows = manifest.rows;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].row, 1);
+    assert!(rows[0].ont.native_vlans.is_empty());
+    assert_eq!(rows[0].ont.service_ports[0].user_vlan, 20);
+}
+
+#[test]
+fn validate_manifest_finds_problems() {
+    let csv = "sn,fsp,description,line_profile_id,service_profile_id,native_vlan\n\
+               HWTC-CB8FBDB4,0/9/2,ok,1,1,20\n\
+               48575443CB8FBDB4,0/9/3,dup,1,1,\n\
+               NOTASERIAL,0/9/2,x,1,1,\n\
+               HWTC-00000001,0/9/2,has space,1,1,4095\n";
+    let manifest = parse_csv_manifest(csv).expect("manifest should parse");
+    let issues = validate_manifest(&manifest.rows);
+
+    let rows_with_issues: Vec<usize> = issues.iter().map(|i| i.row).collect();
+    assert_eq!(rows_with_issues, vec![3, 4, 5, 5]);
+    assert!(issues[0].message.contains("first seen on row 2"));
+}
+
+#[test]
+fn bulk_report_to_csv() {
+    let report = BulkReport {
+        rows: vec![
+            RowResult {
+                row: 1,
+                sn: "HWTC-CB8FBDB4".to_string(),
+                fsp: Fsp {
+                    frame: 0,
+                    slot: 9,
+                    port: 2,
+                },
+                status: RowStatus::Provisioned,
+                ont_id: Some(3),
+                message: None,
+            },
+            RowResult {
+                row: 2,
+                sn: "NOTASERIAL".to_string(),
+                fsp: Fsp::default(),
+                status: RowStatus::Invalid,
+                ont_id: None,
+                message: Some("Invalid serial number, twice".to_string()),
+            },
+        ],
+    };
+
+    assert_eq!(report.count(RowStatus::Provisioned), 1);
+    assert_eq!(
+        report.to_csv(),
+        "row,sn,fsp,status,ont_id,message\n\
+         1,HWTC-CB8FBDB4,0/9/2,provisioned,3,\n\
+         2,NOTASERIAL,0/0/0,invalid,,\"Invalid serial number, twice\"\n"
+    );
+}
+
+fn manifest_row() -> ManifestRow {
+    parse_csv_manifest(CSV)
+        .expect("manifest should parse")
+        .rows
+        .remove(0)
+}
+
+#[test]
+fn provision_row() {
+    let mut olt = FakeOlt::default();
+
+    let result = provision(&mut olt, &manifest_row());
+
+    assert_eq!(result.status, RowStatus::Provisioned);
+    assert_eq!(result.ont_id, Some(0));
+    assert_eq!(
+        olt.changes(),
+        vec![
+            "ont add 0/9/2 HWTC-CB8FBDB4",
+            "ont port native-vlan 0/9/2 0 eth 1 vlan 20",
+            "service-port vlan 1063 0/9/2 ont 0",
+        ]
+    );
+}
+
+#[test]
+fn provision_reports_ont_id_when_failing_mid_way() {
+    let mut olt = FakeOlt::default().fail_on("service-port vlan 1063");
+
+    let result = provision(&mut olt, &manifest_row());
+
+    assert_eq!(result.status, RowStatus::Failed);
+    assert_eq!(result.ont_id, Some(0));
+    assert!(result
+        .message
+        .is_some_and(|m| m.contains("service-port vlan 1063")));
+    assert_eq!(olt.ont_by_sn("HWTC-CB8FBDB4").map(|o| o.id), Some(0));
+}
+
+#[test]
+fn provision_fails_before_ont_add_when_vlan_check_fails() {
+    let mut olt = FakeOlt::default().fail_on("display vlan all");
+
+    let result = provision(&mut olt, &manifest_row());
+
+    assert_eq!(result.status, RowStatus::Failed);
+    assert_eq!(result.ont_id, None);
+    assert!(olt.changes().is_empty());
+}
+
+#[test]
+fn provision_never_sends_invalid_row() {
+    let mut row = manifest_row();
+    row.ont.sn = "NOTASERIAL".to_string();
+
+    let mut olt = FakeOlt::default();
+    let result = provision(&mut olt, &row);
+
+    assert_eq!(result.status, RowStatus::Invalid);
+    assert_eq!(result.ont_id, None);
+    assert!(result
+        .message
+        .is_some_and(|m| m.contains("Invalid serial number")));
+    assert!(olt.calls.is_empty());
+}
@@ -0,0 +1,110 @@
+use oltcore::{clean_configuration, save_status, ConfigurationFilter, SaveStatus};
+
+const PAGES: &str = include_str!("fixtures/current_configuration_pages.txt");
+
+/// Feeds `output` to a filter in chunks of `size` bytes, as the session would
+/// read it, and returns the lines and the number of pager prompts answered.
+fn stream(output: &str, size: usize) -> (String, usize) {
+    let mut filter = ConfigurationFilter::new();
+    let mut config = String::new();
+    let mut prompts = 0;
+    for chunk in output.as_bytes().chunks(size) {
+        for line in filter.push(std::str::from_utf8(chunk).expect("ASCII fixture")) {
+            config.push_str(&line);
+            config.push('\n');
+        }
+        while filter.take_pager_prompt() {
+            prompts += 1;
+        }
+    }
+    assert!(filter.pending().ends_with("MA5800-X7(config)#"));
+    assert!(filter.failure().is_none());
+    (config, prompts)
+}
+
+#[test]
+fn clean_configuration_fixture() {
+    let config = clean_configuration(include_str!("fixtures/current_configuration.txt"));
+    assert!(config.starts_with("[MA5800V100R019: 8208]\n#\n"));
+    assert!(config.ends_with("#\nreturn\n"));
+    assert!(!config.contains("More"));
+    assert!(!config.contains('\u{1b}'));
+    assert!(config.contains("\n  port vlan 1063 0/8 1\n"));
+    assert!(!config.contains("(config)#"));
+}
+
+#[test]
+fn clean_configuration_multiple_pages() {
+    let config = clean_configuration(PAGES);
+    assert!(config.starts_with("[MA5800V100R019: 8208]\n#\n"));
+    assert!(config.ends_with("#\nreturn\n"));
+    assert!(!config.contains("More"));
+    assert!(!config.contains('\u{1b}'));
+    assert!(!config.contains('\r'));
+    assert!(config.contains("\n  vlan 20 smart\n  vlan 1063 smart\n  vlan 1064 smart\n"));
+    assert!(config.contains("\n  ont port native-vlan 0 0 eth 1 vlan 20 priority 0\n"));
+    assert!(config.contains("\n  service-port 1 vlan 1063 gpon 0/5/0 ont 1 "));
+}
+
+#[test]
+fn configuration_filter_matches_clean_configuration_for_any_chunking() {
+    let expected = clean_configuration(PAGES);
+    // Small sizes split the pager prompt and its escape sequences across reads
+    for size in [1, 2, 5, 7, 16, 37, 64, 4096] {
+        let (config, prompts) = stream(PAGES, size);
+        assert_eq!(config, expected, "chunk size {size}");
+        assert_eq!(prompts, 3, "chunk size {size}");
+    }
+}
+
+#[test]
+fn configuration_filter_reports_failure() {
+    let mut filter = ConfigurationFilter::new();
+    let lines = filter.push(
+        "MA5800-X7(config)#display current-configuration\r\n  Failure: System is busy\r\nMA5800-X7(config)#",
+    );
+    assert!(lines.is_empty());
+    assert_eq!(filter.failure(), Some("System is busy"));
+}
+
+#[test]
+fn save_status_from_output() {
+    assert_eq!(
+        save_status("  Command:\n          save\n  It will take several minutes to save configuration file, please wait..."),
+        SaveStatus::InProgress
+    );
+    assert_eq!(
+        save_status("  The data of configuration file had been saved successfully"),
+        SaveStatus::Succeeded
+    );
+    assert_eq!(
+        save_status("  Failure: Saving the configuration file failed"),
+        SaveStatus::Failed
+    );
+}
+
+#[test]
+fn save_status_fixture() {
+    let output = include_str!("fixtures/save.txt");
+    assert_eq!(save_status(output), SaveStatus::Succeeded);
+
+    let waiting = output
+        .split_inclusive('\n')
+        .take_while(|line| !line.contains("successfully"))
+        .collect::<String>();
+    assert_eq!(save_status(&waiting), SaveStatus::InProgress);
+}
+
+#[test]
+fn save_status_matches_whole_lines() {
+    assert_eq!(
+        save_status("  Note: saved successfully unless the board failed\n"),
+        SaveStatus::InProgress
+    );
+    assert_eq!(
+        save_status(
+            "  Configuration file had been saved successfully\n  Failure: System is busy\n"
+        ),
+        SaveStatus::Failed
+    );
+}
@@ -0,0 +1,86 @@
+use oltcore::{
+    diff_configurations, diff_ont_summaries, parse_ont_info_summary, ChangeKind, Fsp, OntKey,
+};
+
+const PORT: Fsp = Fsp {
+    frame: 0,
+    slot: 5,
+    port: 0,
+};
+
+#[test]
+fn diff_identical_configurations_is_empty() {
+    let config = include_str!("fixtures/current_configuration.txt");
+    assert!(diff_configurations(config, config).is_empty());
+}
+
+#[test]
+fn diff_configurations_by_entity() {
+    let before = include_str!("fixtures/current_configuration.txt");
+    let after = before
+        .replace("  vlan 20 smart\n", "  vlan 30 smart\n")
+        .replace("desc \"JFTECH\"", "desc \"JFTECH-2\"")
+        .replace(
+            "  service-port 1 vlan 1063 gpon 0/5/0 ont 1 gemport 20 multi-service user-vlan 20 tag-transform translate\n",
+            "",
+        )
+        .replace("  port vlan 1063 0/8 0\n", "  port vlan 1063 0/9 0\n");
+
+    let diff = diff_configurations(before, &after);
+
+    let vlans: Vec<(u32, ChangeKind)> = diff.vlans.iter().map(|c| (c.key, c.kind)).collect();
+    assert_eq!(
+        vlans,
+        vec![(20, ChangeKind::Removed), (30, ChangeKind::Added)]
+    );
+
+    assert_eq!(diff.port_vlans.len(), 2);
+    assert!(diff.line_profiles.is_empty());
+    assert!(diff.service_profiles.is_empty());
+
+    assert_eq!(diff.onts.len(), 1);
+    assert_eq!(diff.onts[0].key, OntKey { fsp: PORT, id: 1 });
+    assert_eq!(diff.onts[0].kind, ChangeKind::Changed);
+    assert_eq!(
+        diff.onts[0]
+            .after
+            .as_ref()
+            .and_then(|o| o.description.as_deref()),
+        Some("JFTECH-2")
+    );
+
+    assert_eq!(diff.service_ports.len(), 1);
+    assert_eq!(diff.service_ports[0].key, 1);
+    assert_eq!(diff.service_ports[0].kind, ChangeKind::Removed);
+    assert!(diff.service_ports[0].after.is_none());
+}
+
+#[test]
+fn diff_ont_summaries_ignores_measurements() {
+    let before = parse_ont_info_summary(include_str!("fixtures/ont_info_summary.txt"));
+    let mut after = before.clone();
+    let port = after
+        .ports
+        .iter_mut()
+        .find(|p| p.fsp == PORT)
+        .expect("expected port 0/5/0");
+    port.onts[0].rx_power = Some(-30.0);
+    port.onts[1].description = "changed@example.com".to_string();
+    let removed = port.onts.remove(2);
+
+    let changes = diff_ont_summaries(&before, &after);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].kind, ChangeKind::Changed);
+    assert_eq!(
+        changes[0].key.id,
+        before.ports.iter().find(|p| p.fsp == PORT).unwrap().onts[1].id
+    );
+    assert_eq!(changes[1].kind, ChangeKind::Removed);
+    assert_eq!(
+        changes[1].key,
+        OntKey {
+            fsp: PORT,
+            id: removed.id
+        }
+    );
+}
@@ -0,0 +1,262 @@
+mod support;
+
+use std::time::Duration;
+
+use oltcore::migrate::migrate_ont;
+use oltcore::{
+    Error, Fsp, MigrateOptions, MigrationFailure, NativeVlan, OntPortType, RollbackOutcome,
+};
+use support::{FakeOlt, FakeOnt};
+
+const SOURCE: Fsp = Fsp {
+    frame: 0,
+    slot: 9,
+    port: 2,
+};
+
+const DESTINATION: Fsp = Fsp {
+    frame: 0,
+    slot: 9,
+    port: 3,
+};
+
+const SN: &str = "48575443CB8FBDB4";
+
+const fn native_vlan() -> NativeVlan {
+    NativeVlan {
+        port_type: OntPortType::Eth,
+        ont_port: 1,
+        vlan: 20,
+        priority: 0,
+    }
+}
+
+fn olt() -> FakeOlt {
+    FakeOlt::default().with_ont(
+        FakeOnt {
+            fsp: SOURCE,
+            id: 3,
+            sn: SN.to_string(),
+            description: "JFTECH".to_string(),
+            line_profile_id: 60,
+            service_profile_id: 35,
+            native_vlans: vec![native_vlan()],
+        },
+        &[(5, 1063, 20, 20), (6, 100, 21, 100)],
+    )
+}
+
+const fn options() -> MigrateOptions {
+    MigrateOptions {
+        online_timeout: Duration::ZERO,
+        poll_interval: Duration::ZERO,
+    }
+}
+
+fn failure(err: Error) -> MigrationFailure {
+    match err {
+        Error::MigrationFailed(failure) => *failure,
+        other => panic!("expected a migration failure, got {other}"),
+    }
+}
+
+/// Asserts the ONT is back on the source port as it was before the migration.
+fn assert_restored(olt: &FakeOlt) {
+    let ont = olt.ont_by_sn(SN).expect("ONT should exist");
+    assert_eq!((ont.fsp, ont.id), (SOURCE, 3));
+    assert_eq!(ont.description, "JFTECH");
+    assert_eq!((ont.line_profile_id, ont.service_profile_id), (60, 35));
+    assert_eq!(ont.native_vlans, vec![native_vlan()]);
+
+    let vlans: Vec<u32> = olt
+        .service_ports_of(SOURCE, 3)
+        .iter()
+        .map(|sp| sp.vlan)
+        .collect();
+    assert_eq!(vlans, vec![1063, 100]);
+    assert!(olt.service_ports_of(DESTINATION, 0).is_empty());
+}
+
+#[test]
+fn migrate_moves_profiles_native_vlans_and_service_ports() {
+    let mut olt = olt();
+
+    let report = migrate_ont(&mut olt, SN, DESTINATION, &options()).expect("migration");
+
+    assert_eq!(
+        olt.changes(),
+        vec![
+            "undo service-port 5",
+            "undo service-port 6",
+            "ont delete 0/9/2 3",
+            "ont add 0/9/3 48575443CB8FBDB4",
+            "ont port native-vlan 0/9/3 0 eth 1 vlan 20",
+            "service-port vlan 1063 0/9/3 ont 0",
+            "service-port vlan 100 0/9/3 ont 0",
+        ]
+    );
+    assert_eq!(report.source_ont_id, 3);
+    assert_eq!(report.destination_ont_id, 0);
+    assert_eq!(report.removed_service_ports, vec![5, 6]);
+    assert_eq!(report.native_vlans, vec![native_vlan()]);
+    assert!(report.online);
+
+    let ont = olt.ont_by_sn(SN).expect("ONT should exist");
+    assert_eq!((ont.fsp, ont.id), (DESTINATION, 0));
+    assert_eq!(ont.description, "JFTECH");
+    assert_eq!((ont.line_profile_id, ont.service_profile_id), (60, 35));
+    assert_eq!(ont.native_vlans, vec![native_vlan()]);
+
+    let flows: Vec<(u32, Option<u32>, String)> = olt
+        .service_ports_of(DESTINATION, 0)
+        .iter()
+        .map(|sp| (sp.vlan, sp.gemport, sp.flow_para.clone()))
+        .collect();
+    assert_eq!(
+        flows,
+        vec![
+            (1063, Some(20), "20".to_string()),
+            (100, Some(21), "100".to_string())
+        ]
+    );
+}
+
+#[test]
+fn migrate_fails_without_changes_before_first_undo() {
+    let mut olt = olt().fail_on("undo service-port 5");
+
+    let err = migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error");
+
+    assert!(matches!(err, Error::CommandFailed(_)));
+    assert!(olt.changes().is_empty());
+    assert_restored(&olt);
+}
+
+#[test]
+fn migrate_checks_vlans_before_changes() {
+    let mut olt = olt().fail_on("display vlan all");
+
+    let err = migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error");
+
+    assert!(matches!(err, Error::CommandFailed(_)));
+    assert!(olt.changes().is_empty());
+    assert_restored(&olt);
+}
+
+#[test]
+fn migrate_restores_service_ports_when_source_delete_fails() {
+    let mut olt = olt().fail_on("ont delete 0/9/2");
+
+    let failure =
+        failure(migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error"));
+
+    assert_eq!(failure.removed_service_ports, vec![5, 6]);
+    assert_eq!(failure.destination_ont_id, None);
+    assert!(matches!(failure.rollback, RollbackOutcome::Restored));
+    assert_eq!(
+        olt.changes()[2..],
+        [
+            "service-port vlan 1063 0/9/2 ont 3",
+            "service-port vlan 100 0/9/2 ont 3"
+        ]
+    );
+    assert_restored(&olt);
+}
+
+#[test]
+fn migrate_rolls_back_when_destination_rejects_ont() {
+    let mut olt = olt().fail_on("ont add 0/9/3");
+
+    let failure =
+        failure(migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error"));
+
+    assert!(matches!(failure.error, Error::CommandFailed(_)));
+    assert_eq!(failure.removed_service_ports, vec![5, 6]);
+    assert_eq!(failure.destination_ont_id, None);
+    assert!(matches!(failure.rollback, RollbackOutcome::Restored));
+    assert_eq!(
+        olt.changes()[3..],
+        [
+            "ont add 0/9/2 3 48575443CB8FBDB4",
+            "ont port native-vlan 0/9/2 3 eth 1 vlan 20",
+            "service-port vlan 1063 0/9/2 ont 3",
+            "service-port vlan 100 0/9/2 ont 3"
+        ]
+    );
+    assert_restored(&olt);
+}
+
+#[test]
+fn migrate_rolls_back_destination_when_configuring_fails() {
+    let mut olt = olt().fail_on("service-port vlan 100 0/9/3");
+
+    let failure =
+        failure(migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error"));
+
+    assert_eq!(failure.destination_ont_id, Some(0));
+    assert!(matches!(failure.rollback, RollbackOutcome::Restored));
+    assert_eq!(
+        olt.changes()[6..],
+        [
+            "undo service-port 0",
+            "ont delete 0/9/3 0",
+            "ont add 0/9/2 3 48575443CB8FBDB4",
+            "ont port native-vlan 0/9/2 3 eth 1 vlan 20",
+            "service-port vlan 1063 0/9/2 ont 3",
+            "service-port vlan 100 0/9/2 ont 3"
+        ]
+    );
+    assert_restored(&olt);
+}
+
+#[test]
+fn migrate_reports_failed_rollback() {
+    let mut olt = olt().fail_on("ont add");
+
+    let err = migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error");
+    let message = err.to_string();
+    let failure = failure(err);
+
+    assert_eq!(failure.removed_service_ports, vec![5, 6]);
+    assert!(matches!(failure.rollback, RollbackOutcome::Failed(_)));
+    assert!(message.contains("removed service ports [5, 6]"));
+    assert!(message.contains("rollback failed"));
+    assert!(olt.ont_by_sn(SN).is_none());
+}
+
+#[test]
+fn migrate_restores_ont_without_service_ports() {
+    let mut olt = FakeOlt::default()
+        .with_ont(
+            FakeOnt {
+                fsp: SOURCE,
+                id: 3,
+                sn: SN.to_string(),
+                description: "JFTECH".to_string(),
+                line_profile_id: 60,
+                service_profile_id: 35,
+                native_vlans: vec![native_vlan()],
+            },
+            &[],
+        )
+        .fail_on("ont add 0/9/3");
+
+    let failure =
+        failure(migrate_ont(&mut olt, SN, DESTINATION, &options()).expect_err("expected error"));
+
+    assert!(failure.removed_service_ports.is_empty());
+    assert!(matches!(failure.rollback, RollbackOutcome::Restored));
+    assert_eq!(
+        olt.changes(),
+        [
+            "ont delete 0/9/2 3",
+            "ont add 0/9/2 3 48575443CB8FBDB4",
+            "ont port native-vlan 0/9/2 3 eth 1 vlan 20"
+        ]
+    );
+
+    let ont = olt.ont_by_sn(SN).expect("ONT should exist");
+    assert_eq!((ont.fsp, ont.id), (SOURCE, 3));
+    assert_eq!((ont.line_profile_id, ont.service_profile_id), (60, 35));
+    assert_eq!(ont.native_vlans, vec![native_vlan()]);
+}
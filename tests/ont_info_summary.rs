@@ -96,3 +96,121 @@ fn parse_ont_info_summary_other_ports() {
     assert_eq!(ont.tx_power, None);
     assert_eq!(ont.description, "libertelecom-29899-25");
 }
+
+#[test]
+fn ont_info_summary_aggregates() {
+    let summary = parse_ont_info_summary(include_str!("fixtures/ont_info_summary.txt"));
+
+    let boards = summary.boards();
+    let slots: Vec<u32> = boards.iter().map(|b| b.slot).collect();
+    assert_eq!(slots, vec![5, 6]);
+
+    let board = &boards[0];
+    assert_eq!(board.frame, 0);
+    assert_eq!(board.ports, 10);
+    assert_eq!(
+        board.total_onts,
+        76 + 16 + 58 + 9 + 46 + 65 + 100 + 63 + 2 + 42
+    );
+    assert_eq!(
+        board.online_onts,
+        72 + 12 + 51 + 5 + 38 + 51 + 97 + 54 + 1 + 36
+    );
+
+    assert_eq!(
+        summary.total_onts(),
+        boards.iter().map(|b| b.total_onts).sum::<u32>()
+    );
+    assert_eq!(
+        summary.offline_onts(),
+        summary.total_onts() - summary.online_onts()
+    );
+
+    let port = summary
+        .port(Fsp {
+            frame: 0,
+            slot: 6,
+            port: 14,
+        })
+        .expect("expected port 0/6/14");
+    assert_eq!(port.total_onts, 13);
+}
+
+#[test]
+fn parse_ont_info_summary_board_form() {
+    let summary = parse_ont_info_summary(include_str!("fixtures/ont_info_summary_board.txt"));
+
+    let ports: Vec<(Fsp, u32, usize)> = summary
+        .ports
+        .iter()
+        .map(|p| (p.fsp, p.total_onts, p.onts.len()))
+        .collect();
+    assert_eq!(
+        ports,
+        vec![
+            (
+                Fsp {
+                    frame: 0,
+                    slot: 5,
+                    port: 0
+                },
+                2,
+                2
+            ),
+            (
+                Fsp {
+                    frame: 0,
+                    slot: 5,
+                    port: 1
+                },
+                0,
+                0
+            ),
+            (
+                Fsp {
+                    frame: 0,
+                    slot: 5,
+                    port: 2
+                },
+                1,
+                1
+            ),
+        ]
+    );
+    assert_eq!(summary.ports[0].onts[1].run_state, "offline");
+    assert_eq!(summary.ports[2].onts[0].sn, "54504C47CEFFB0C8");
+
+    let boards = summary.boards();
+    assert_eq!(boards.len(), 1);
+    assert_eq!(boards[0].slot, 5);
+    assert_eq!(boards[0].ports, 2);
+    assert_eq!((boards[0].total_onts, boards[0].online_onts), (3, 2));
+}
+
+#[test]
+fn parse_ont_info_summary_frame_form() {
+    let summary = parse_ont_info_summary(include_str!("fixtures/ont_info_summary_frame.txt"));
+
+    assert_eq!(summary.ports.len(), 5);
+    assert_eq!(summary.total_onts(), 4);
+    assert_eq!(summary.online_onts(), 2);
+
+    let ont = &summary
+        .port(Fsp {
+            frame: 0,
+            slot: 6,
+            port: 3,
+        })
+        .expect("expected port 0/6/3")
+        .onts[0];
+    assert_eq!(ont.id, 4);
+    assert_eq!(ont.last_down_cause, "LOSi/LOBi");
+    assert_eq!(ont.description, "TA791-francisco@gserv");
+
+    let boards: Vec<(u32, u32, u32)> = summary
+        .boards()
+        .iter()
+        .map(|b| (b.slot, b.ports, b.total_onts))
+        .collect();
+    assert_eq!(boards, vec![(5, 2, 3), (6, 1, 1)]);
+}
@@ -0,0 +1,140 @@
+mod support;
+
+use std::time::Duration;
+
+use oltcore::{
+    parse_ont_info_list, parse_ont_info_summary, search_summary, DescriptionQuery, Fsp,
+    OntSearchCache, OntSearchHit, SearchTarget,
+};
+use support::{FakeOlt, FakeOnt};
+
+const TTL: Duration = Duration::from_secs(60);
+
+const fn fsp(slot: u32, port: u32) -> Fsp {
+    Fsp {
+        frame: 0,
+        slot,
+        port,
+    }
+}
+
+fn ont(fsp: Fsp, id: u32, description: &str) -> FakeOnt {
+    FakeOnt {
+        fsp,
+        id,
+        sn: format!("48575443CB8FBD{id:02}"),
+        description: description.to_string(),
+        line_profile_id: 60,
+        service_profile_id: 35,
+        native_vlans: Vec::new(),
+    }
+}
+
+fn olt() -> FakeOlt {
+    FakeOlt::default()
+        .with_ont(ont(fsp(5, 0), 0, "rozenilda.lins@gserv.net"), &[])
+        .with_ont(ont(fsp(5, 3), 1, "JFTECH"), &[])
+        .with_ont(ont(fsp(9, 2), 2, "TA786-vitor@gserv.net"), &[])
+}
+
+fn descriptions(hits: &[OntSearchHit]) -> Vec<&str> {
+    hits.iter().map(|h| h.description.as_str()).collect()
+}
+
+#[test]
+fn parse_ont_info_by_desc_fixture() {
+    let list = parse_ont_info_list(include_str!("fixtures/ont_info_by_desc.txt"));
+    let hits: Vec<OntSearchHit> = list.onts.iter().map(OntSearchHit::from).collect();
+
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[1].fsp.slot, 6);
+    assert_eq!(hits[1].id, 12);
+    assert_eq!(hits[1].run_state, "offline");
+    assert_eq!(hits[1].description, "JFTECH");
+}
+
+#[test]
+fn search_summary_by_substring_and_regex() {
+    let summary = parse_ont_info_summary(include_str!("fixtures/ont_info_summary.txt"));
+
+    let hits = search_summary(&summary, &DescriptionQuery::substring("ROZENILDA"));
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].fsp.port, 0);
+    assert_eq!(hits[0].id, 0);
+    assert_eq!(hits[0].sn, "4D4B5047B4BABE7C");
+
+    let query = DescriptionQuery::regex(r"^TA786-").expect("valid pattern");
+    let hits = search_summary(&summary, &query);
+    assert!(hits.len() >= 2);
+    assert!(hits.iter().all(|h| h.description.starts_with("TA786-")));
+
+    assert!(DescriptionQuery::regex("(").is_err());
+}
+
+#[test]
+fn cache_searches_preloaded_summary() {
+    let summary = parse_ont_info_summary(include_str!("fixtures/ont_info_summary.txt"));
+    let mut cache = OntSearchCache::from_summary(summary, TTL);
+    assert!(!cache.is_stale());
+    assert_eq!(
+        cache
+            .search_cached(&DescriptionQuery::substring("TA786-vitor@"))
+            .len(),
+        1
+    );
+
+    cache.invalidate();
+    assert!(cache.is_stale());
+    assert!(cache
+        .search_cached(&DescriptionQuery::substring("TA786-vitor@"))
+        .is_empty());
+}
+
+#[test]
+fn cache_searches_every_port_of_a_frame() {
+    let mut olt = olt();
+    let mut cache = OntSearchCache::for_frames(vec![0], TTL);
+
+    let hits = cache
+        .search(&mut olt, &DescriptionQuery::substring("gserv"))
+        .expect("search");
+
+    assert_eq!(
+        descriptions(&hits),
+        vec!["rozenilda.lins@gserv.net", "TA786-vitor@gserv.net"]
+    );
+    assert_eq!(hits[1].fsp, fsp(9, 2));
+    assert_eq!(olt.calls, vec!["display ont info summary 0"]);
+
+    cache
+        .search(&mut olt, &DescriptionQuery::substring("JFTECH"))
+        .expect("search");
+    assert_eq!(olt.calls.len(), 1);
+}
+
+#[test]
+fn cache_refresh_skips_failing_ports() {
+    let mut olt = olt().fail_on("display ont info summary 0/5/3");
+    let mut cache = OntSearchCache::new(vec![fsp(5, 0), fsp(5, 3), fsp(9, 2)], TTL);
+
+    let hits = cache
+        .search(&mut olt, &DescriptionQuery::substring("@"))
+        .expect("search");
+
+    assert_eq!(
+        descriptions(&hits),
+        vec!["rozenilda.lins@gserv.net", "TA786-vitor@gserv.net"]
+    );
+    assert_eq!(cache.skipped().len(), 1);
+    assert_eq!(cache.skipped()[0].0, SearchTarget::Port(fsp(5, 3)));
+}
+
+#[test]
+fn cache_refresh_fails_when_no_target_is_read() {
+    let mut olt = olt().fail_on("display ont info summary");
+    let mut cache = OntSearchCache::for_frames(vec![0], TTL);
+
+    assert!(cache.refresh(&mut olt).is_err());
+    assert!(cache.is_stale());
+    assert!(cache.skipped().is_empty());
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use oltcore::{counter_rates, parse_ont_counters, parse_ont_traffic, OntCounters};
+
+#[test]
+fn parse_ont_counters_fixtures() {
+    let line_quality = parse_ont_counters(include_str!("fixtures/ont_line_quality.txt"));
+    assert_eq!(line_quality.upstream_bip_errors, Some(12));
+    assert_eq!(line_quality.downstream_bip_errors, Some(3));
+    assert_eq!(line_quality.upstream_fec_corrected_codewords, Some(51));
+    assert_eq!(line_quality.downstream_fec_uncorrectable_codewords, Some(0));
+    assert_eq!(line_quality.gem_hec_errors, Some(1));
+    assert_eq!(line_quality.upstream_bytes, None);
+
+    let statistics = parse_ont_counters(include_str!("fixtures/ont_statistics.txt"));
+    assert_eq!(statistics.upstream_frames, Some(1_234_567));
+    assert_eq!(statistics.downstream_bytes, Some(1_987_654_321));
+    assert_eq!(statistics.upstream_dropped_frames, Some(15));
+
+    let merged = statistics.merge(line_quality);
+    assert_eq!(merged.upstream_bytes, Some(987_654_321));
+    assert_eq!(merged.gem_hec_errors, Some(1));
+}
+
+#[test]
+fn parse_ont_traffic_fixture() {
+    let traffic = parse_ont_traffic(include_str!("fixtures/ont_traffic.txt"));
+    assert_eq!(traffic.len(), 3);
+    assert_eq!(traffic[0].ont_id, 0);
+    assert_eq!(traffic[0].upstream_kbps, 1524);
+    assert_eq!(traffic[0].downstream_kbps, 30218);
+    assert_eq!(traffic[2].ont_id, 5);
+}
+
+#[test]
+fn counter_rates_from_two_samples() {
+    let earlier = OntCounters {
+        upstream_bytes: Some(1_000),
+        downstream_bytes: Some(10_000),
+        upstream_bip_errors: Some(5),
+        gem_hec_errors: Some(7),
+        ..OntCounters::default()
+    };
+    let later = OntCounters {
+        upstream_bytes: Some(11_000),
+        downstream_bytes: Some(50_000),
+        upstream_bip_errors: Some(25),
+        gem_hec_errors: Some(3),
+        ..OntCounters::default()
+    };
+
+    let rates = counter_rates(&earlier, &later, Duration::from_secs(10));
+    assert_eq!(rates.upstream_bps, Some(8_000.0));
+    assert_eq!(rates.downstream_bps, Some(32_000.0));
+    assert_eq!(rates.upstream_bip_errors_per_second, Some(2.0));
+    assert_eq!(rates.gem_hec_errors_per_second, None);
+    assert_eq!(rates.downstream_bip_errors_per_second, None);
+
+    let instant = counter_rates(&earlier, &later, Duration::ZERO);
+    assert_eq!(instant.upstream_bps, None);
+}
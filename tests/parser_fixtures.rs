@@ -1,6 +1,12 @@
-use oltcore::parser::{extract_ont_id, parse_optical_info};
+use oltcore::parser::{
+    extract_ont_id, parse_ont_eth_port_states, parse_ont_ipconfig, parse_ont_optical_levels,
+    parse_ont_port_attributes, parse_ont_register_info, parse_ont_versions, parse_ont_wan_info,
+    parse_optical_info,
+};
 use oltcore::{
-    parse_display_board, parse_ont_autofind, parse_ont_info, parse_service_ports, Fsp, ServicePort,
+    parse_board_detail, parse_display_board, parse_ont_autofind, parse_ont_info,
+    parse_ont_info_list, parse_service_port_table, parse_service_ports, Fsp, OntDownCause,
+    ServicePort,
 };
 
 #[test]
@@ -85,9 +91,46 @@ fn parse_service_ports_fixture() {
         ports,
         vec![ServicePort {
             index: 68,
-            vlan: 1063
-        },]
+            vlan: 1063,
+            vlan_attr: "common".to_string(),
+            port_type: "gpon".to_string(),
+            fsp: Fsp {
+                frame: 0,
+                slot: 9,
+                port: 2,
+            },
+            ont_id: Some(0),
+            gemport: Some(20),
+            flow_type: "vlan".to_string(),
+            flow_para: "20".to_string(),
+            inbound_traffic_table: Some(10),
+            outbound_traffic_table: Some(10),
+            state: "up".to_string(),
+        }]
     );
+    assert_eq!(ports[0].user_vlan(), Some(20));
+}
+
+#[test]
+fn parse_service_port_table_fixture() {
+    let output = include_str!("fixtures/service_port_all.txt");
+    let table = parse_service_port_table(output);
+    assert_eq!(table.ports.len(), 5);
+    assert_eq!((table.total, table.up, table.down), (5, 4, 1));
+
+    let eth = &table.ports[0];
+    assert_eq!(eth.port_type, "eth");
+    assert_eq!(eth.fsp.to_string(), "0/17/0");
+    assert_eq!(eth.ont_id, None);
+    assert_eq!(eth.gemport, None);
+    assert_eq!(eth.inbound_traffic_table, None);
+
+    assert_eq!(table.ports[2].state, "down");
+    assert_eq!(table.ports[3].vlan_attr, "stacking");
+    assert_eq!(table.ports[3].fsp.to_string(), "0/10/5");
+    assert_eq!(table.ports[3].ont_id, Some(12));
+    assert_eq!(table.ports[4].flow_type, "untag");
+    assert_eq!(table.ports[4].user_vlan(), None);
 }
 
 #[test]
@@ -117,3 +160,250 @@ fn parse_display_board_fixture() {
     assert_eq!(standby.board_name.as_deref(), Some("H902MPLA"));
     assert_eq!(standby.status.as_deref(), Some("Standby_normal"));
 }
+
+#[test]
+fn parse_ont_port_attributes_fixture() {
+    let output = include_str!("fixtures/ont_port_attribute.txt");
+    let ports = parse_ont_port_attributes(output);
+    assert_eq!(ports.len(), 4);
+
+    assert_eq!(ports[0].ont_id, 0);
+    assert_eq!(ports[0].port_id, 1);
+    assert_eq!(ports[0].port_type, "GE");
+    assert_eq!(ports[0].native_vlan, Some(20));
+    assert_eq!(ports[0].priority, Some(0));
+    assert_eq!(ports[2].port_type, "FE");
+    assert_eq!(ports[2].active_state, "off");
+    assert_eq!(ports[3].native_vlan, None);
+    assert_eq!(ports[3].priority, None);
+}
+
+#[test]
+fn parse_board_detail_fixture() {
+    let output = include_str!("fixtures/display_board_detail.txt");
+    let detail = parse_board_detail(output).expect("expected board detail");
+    assert_eq!(detail.board_name, "H901GPUF");
+    assert_eq!(detail.status, "Normal");
+    assert_eq!(detail.online_state, None);
+    assert_eq!(detail.ports.len(), 4);
+
+    let first = &detail.ports[0];
+    assert_eq!(first.port_type, "GPON");
+    assert_eq!(first.port_status, "Enable");
+    assert_eq!(first.max_distance_km, Some(20));
+    assert!(first.has_optical_module());
+    assert_eq!((first.ont_total, first.ont_online), (Some(62), Some(58)));
+
+    let last = &detail.ports[3];
+    assert!(!last.has_optical_module());
+    assert_eq!(last.min_distance_km, None);
+    assert_eq!(last.ont_total, None);
+
+    assert!(parse_board_detail(include_str!("fixtures/display_board.txt")).is_none());
+}
+
+#[test]
+fn parse_ont_optical_levels_fixture() {
+    let output = include_str!("fixtures/optical_info_all.txt");
+    let levels = parse_ont_optical_levels(output);
+    let ids: Vec<u32> = levels.iter().map(|l| l.ont_id).collect();
+    assert_eq!(ids, vec![0, 1, 2, 5]);
+
+    let first = &levels[0];
+    assert_eq!(first.rx_power, Some(-15.93));
+    assert_eq!(first.tx_power, Some(2.34));
+    assert_eq!(first.olt_rx_power, Some(-23.67));
+    assert_eq!(first.temperature, Some(37));
+    assert_eq!(first.voltage, Some(3.26));
+    assert_eq!(first.bias_current, Some(9.0));
+
+    let offline = &levels[2];
+    assert_eq!(offline.rx_power, None);
+    assert_eq!(offline.temperature, None);
+    assert_eq!(offline.bias_current, None);
+}
+
+#[test]
+fn parse_ont_versions_fixture() {
+    let output = include_str!("fixtures/ont_version.txt");
+    let versions = parse_ont_versions(output);
+    assert_eq!(versions.len(), 2);
+
+    let huawei = &versions[0];
+    assert_eq!(huawei.fsp.to_string(), "0/9/2");
+    assert_eq!(huawei.ont_id, 0);
+    assert_eq!(huawei.vendor_id, "HWTC");
+    assert_eq!(huawei.ont_version, "21C7.A");
+    assert!(huawei.product_description.contains("(CLASS B+/PRODUCT ID:"));
+    assert_eq!(huawei.equipment_id, "EG8145X6-10");
+    assert_eq!(huawei.main_software_version, "V5R021C00S125");
+    assert_eq!(
+        huawei.standby_software_version.as_deref(),
+        Some("V5R020C10S115")
+    );
+    assert_eq!(huawei.oui_version.as_deref(), Some("CTC3.0"));
+
+    let zte = &versions[1];
+    assert_eq!(zte.ont_id, 1);
+    assert_eq!(zte.vendor_id, "ZTEG");
+    assert_eq!(zte.standby_software_version, None);
+    assert_eq!(zte.oui_version, None);
+}
+
+#[test]
+fn parse_ont_versions_skips_invalid_ont_id() {
+    let output = include_str!("fixtures/ont_version.txt").replace(
+        "ONT-ID                   : 0",
+        "ONT-ID                   : -",
+    );
+    let versions = parse_ont_versions(&output);
+
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].ont_id, 1);
+    assert_eq!(versions[0].vendor_id, "ZTEG");
+}
+
+#[test]
+fn parse_ont_wan_info_fixture() {
+    let output = include_str!("fixtures/ont_wan_info.txt");
+    let wans = parse_ont_wan_info(output);
+    assert_eq!(wans.len(), 2);
+
+    let tr069 = &wans[0];
+    assert_eq!(tr069.index, 1);
+    assert_eq!(tr069.service_type, "TR069");
+    assert_eq!(tr069.ipv4_access_type, "DHCP");
+    assert_eq!(tr069.secondary_dns, None);
+    assert_eq!(tr069.pppoe_username, None);
+    assert_eq!(tr069.ipv6_address, None);
+    assert!(tr069.has_ipv4());
+
+    let internet = &wans[1];
+    assert_eq!(internet.name, "2_INTERNET_R_VID_1063");
+    assert_eq!(internet.connection_type, "IP routed");
+    assert_eq!(internet.ipv4_address.as_deref(), Some("100.64.12.34"));
+    assert_eq!(internet.gateway.as_deref(), Some("100.64.0.1"));
+    assert_eq!(internet.vlan, Some(1063));
+    assert_eq!(internet.mac_address.as_deref(), Some("00E0-FC12-3456"));
+    assert_eq!(internet.pppoe_username.as_deref(), Some("jftech@provider"));
+    assert_eq!(
+        internet.ipv6_address.as_deref(),
+        Some("2804:14c:5b80:8000::1a2b")
+    );
+    assert_eq!(
+        internet.ipv6_prefix.as_deref(),
+        Some("2804:14c:5b80:8100::/56")
+    );
+}
+
+#[test]
+fn parse_ont_ipconfig_fixture() {
+    let output = include_str!("fixtures/ont_ipconfig.txt");
+    let configs = parse_ont_ipconfig(output);
+    assert_eq!(configs.len(), 1);
+
+    let config = &configs[0];
+    assert_eq!(config.index, 0);
+    assert_eq!(config.config_type, "DHCP");
+    assert_eq!(config.ip_address.as_deref(), Some("192.168.100.10"));
+    assert_eq!(config.secondary_dns.as_deref(), Some("0.0.0.0"));
+    assert_eq!(config.vlan, Some(100));
+    assert_eq!(config.priority, Some(0));
+}
+
+#[test]
+fn parse_ont_eth_port_states_fixture() {
+    let output = include_str!("fixtures/ont_port_state.txt");
+    let states = parse_ont_eth_port_states(output);
+    assert_eq!(states.len(), 4);
+
+    let first = &states[0];
+    assert_eq!((first.ont_id, first.port_id), (0, 1));
+    assert_eq!(first.port_type, "GE");
+    assert_eq!(first.speed_mbps, Some(1000));
+    assert_eq!(first.duplex.as_deref(), Some("full"));
+    assert_eq!(first.ring_status, "disable");
+    assert!(first.is_link_up());
+
+    assert!(!states[1].is_link_up());
+    assert_eq!(states[1].speed_mbps, None);
+    assert_eq!(states[1].duplex, None);
+    assert_eq!(states[2].duplex.as_deref(), Some("half"));
+}
+
+#[test]
+fn parse_ont_register_info_fixture() {
+    let output = include_str!("fixtures/ont_register_info.txt");
+    let records = parse_ont_register_info(output);
+    assert_eq!(records.len(), 3);
+
+    let current = &records[0];
+    assert_eq!(current.index, 1);
+    assert_eq!(current.auth_type, "SN");
+    assert_eq!(current.sn, "48575443CB8FBDB4");
+    assert_eq!(
+        current.up_time.as_deref(),
+        Some("2026-01-20 08:44:35+08:00")
+    );
+    assert_eq!(current.down_time, None);
+    assert_eq!(current.down_cause, OntDownCause::Unknown);
+
+    assert_eq!(records[1].down_cause, OntDownCause::DyingGasp);
+    assert!(!records[1].down_cause.is_optical());
+    assert_eq!(records[2].down_cause, OntDownCause::LosiLobi);
+    assert!(records[2].down_cause.is_optical());
+}
+
+#[test]
+fn ont_down_cause_round_trips_as_string() {
+    let info = parse_ont_info(include_str!("fixtures/ont_info.txt")).expect("expected ONT info");
+    assert_eq!(info.last_down_cause, OntDownCause::DyingGasp);
+
+    let json = serde_json::to_string(&OntDownCause::Lofi).expect("serialize");
+    assert_eq!(json, "\"LOFi\"");
+    let other: OntDownCause = serde_json::from_str("\"ring\"").expect("deserialize");
+    assert_eq!(other, OntDownCause::Other("ring".to_string()));
+    assert_eq!(other, "ring");
+}
+
+#[test]
+fn parse_ont_info_list_fixture() {
+    let output = include_str!("fixtures/ont_info_all.txt");
+    let list = parse_ont_info_list(output);
+
+    assert_eq!(
+        list.fsp,
+        Fsp {
+            frame: 0,
+            slot: 5,
+            port: 0,
+        }
+    );
+    assert_eq!(list.total_onts, 4);
+    assert_eq!(list.online_onts, 2);
+    assert_eq!(list.onts.len(), 4);
+
+    let first = &list.onts[0];
+    assert_eq!(first.fsp, list.fsp);
+    assert_eq!(first.sn, "48575443CB8FBDB4");
+    assert_eq!(first.control_flag, "active");
+    assert_eq!(first.run_state, "online");
+    assert_eq!(first.config_state, "normal");
+    assert_eq!(first.match_state, "match");
+    assert_eq!(first.protect_side, "no");
+    assert_eq!(first.description, "JFTECH");
+
+    assert_eq!(list.onts[2].control_flag, "deactivated");
+    assert_eq!(list.onts[2].description, "");
+    assert_eq!(list.onts[3].description, "Rua das Flores 120 apto 3");
+
+    let ids: Vec<u32> = list.config_mismatches().iter().map(|o| o.id).collect();
+    assert_eq!(ids, vec![1, 3]);
+}
+
+#[test]
+fn parse_ont_info_list_empty() {
+    let list = parse_ont_info_list("");
+    assert!(list.onts.is_empty());
+    assert_eq!(list.total_onts, 0);
+}
@@ -1,5 +1,5 @@
 use oltcore::parser::{extract_ont_id, parse_optical_info};
-use oltcore::{parse_ont_autofind, parse_ont_info, parse_service_ports, ServicePort};
+use oltcore::{parse_ont_autofind, parse_ont_info, parse_service_ports};
 use proptest::prelude::*;
 
 proptest! {
@@ -24,12 +24,11 @@ proptest! {
         for (index, vlan) in &entries {
             output.push_str(&format!("     {index:4} {vlan:4} common   gpon 0/9 /2  0    20    vlan  20         10   10   up\n"));
         }
-        let ports = parse_service_ports(&output);
-        let expected: Vec<ServicePort> = entries
-            .into_iter()
-            .map(|(index, vlan)| ServicePort { index, vlan })
+        let ports: Vec<(u32, u32)> = parse_service_ports(&output)
+            .iter()
+            .map(|port| (port.index, port.vlan))
             .collect();
-        prop_assert_eq!(ports, expected);
+        prop_assert_eq!(ports, entries);
     }
 
     #[test]
@@ -0,0 +1,74 @@
+use oltcore::{parse_pon_optical_module, parse_pon_port_info, parse_pon_port_state, Fsp};
+
+const PORT: Fsp = Fsp {
+    frame: 0,
+    slot: 9,
+    port: 2,
+};
+
+#[test]
+fn parse_pon_port_state_fixture() {
+    let output = include_str!("fixtures/port_state.txt");
+    let state = parse_pon_port_state(output).expect("expected port state");
+
+    assert_eq!(state.fsp, PORT);
+    assert_eq!(state.optical_module_status, "Online");
+    assert!(state.is_online());
+    assert_eq!(state.laser_state, "Normal");
+    assert_eq!(state.available_bandwidth_kbps, Some(1_238_110));
+    assert_eq!(state.temperature, Some(41));
+    assert_eq!(state.bias_current, Some(24.0));
+    assert_eq!(state.supply_voltage, Some(3.27));
+    assert_eq!(state.tx_power, Some(3.63));
+    assert_eq!(state.illegal_rogue_ont, "Inexistent");
+    assert_eq!(state.max_distance_km, Some(20));
+    assert_eq!(state.wavelength_nm, Some(1490));
+}
+
+#[test]
+fn parse_pon_port_info_fixture() {
+    let output = include_str!("fixtures/port_info.txt");
+    let info = parse_pon_port_info(output).expect("expected port info");
+
+    assert_eq!(info.fsp, PORT);
+    assert_eq!(info.port_type, "GPON");
+    assert_eq!(info.admin_state, "Activate");
+    assert_eq!(info.operational_state, "Up");
+    assert_eq!(info.min_distance_km, Some(0));
+    assert_eq!(info.max_distance_km, Some(20));
+    assert_eq!(info.ont_autofind, "Enable");
+    assert_eq!(info.ont_total, Some(24));
+    assert_eq!(info.ont_online, Some(22));
+}
+
+#[test]
+fn parse_pon_optical_module_fixture() {
+    let output = include_str!("fixtures/port_ddm_info.txt");
+    let module = parse_pon_optical_module(output).expect("expected optical module");
+
+    assert_eq!(module.fsp, PORT);
+    assert_eq!(module.module_sub_type, "CLASS C+");
+    assert_eq!(module.vendor_name, "HUAWEI");
+    assert_eq!(module.vendor_pn, "SFP-GPON-C+");
+    assert_eq!(module.vendor_sn, "4851A3B0C1D2E3F4");
+    assert_eq!(module.wavelength_nm, Some(1490));
+    assert_eq!(module.tx_power, Some(3.63));
+}
+
+#[test]
+fn parse_pon_port_empty_is_none() {
+    assert!(parse_pon_port_state("").is_none());
+    assert!(parse_pon_port_info("Failure: The port does not exist").is_none());
+    assert!(parse_pon_optical_module("").is_none());
+}
+
+#[test]
+fn parse_pon_port_skips_unparsable_fsp() {
+    let output = format!(
+        "  F/S/P                                  : -\n{}",
+        include_str!("fixtures/port_info.txt")
+    );
+    let info = parse_pon_port_info(&output).expect("expected port info");
+    assert_eq!(info.fsp, PORT);
+    assert_eq!(info.ont_total, Some(24));
+}
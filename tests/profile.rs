@@ -0,0 +1,169 @@
+use oltcore::ssh::SessionLevel;
+use oltcore::{
+    dba_profile_add_command, parse_dba_profiles, parse_line_profile, parse_profile_summaries,
+    parse_service_profile, DbaBandwidth, OntPortType, PortCount,
+};
+
+#[test]
+fn parse_profile_summaries_fixture() {
+    let profiles = parse_profile_summaries(include_str!("fixtures/ont_lineprofile_all.txt"));
+    assert_eq!(profiles.len(), 3);
+    assert_eq!(profiles[1].id, 60);
+    assert_eq!(profiles[1].name, "FTTH_1063");
+    assert_eq!(profiles[1].binding_times, 412);
+}
+
+#[test]
+fn parse_line_profile_fixture() {
+    let profile = parse_line_profile(include_str!("fixtures/ont_lineprofile.txt"))
+        .expect("expected line profile");
+
+    assert_eq!(profile.id, 61);
+    assert_eq!(profile.name, "FTTH_1063_IPTV");
+    assert_eq!(profile.access_type, "GPON");
+    assert_eq!(profile.mapping_mode, "VLAN");
+    assert_eq!(profile.binding_times, Some(37));
+
+    let tconts: Vec<(u32, u32)> = profile
+        .tconts
+        .iter()
+        .map(|t| (t.id, t.dba_profile_id))
+        .collect();
+    assert_eq!(tconts, vec![(0, 1), (4, 10), (5, 11)]);
+
+    assert_eq!(profile.gem_ports.len(), 2);
+    let iptv = &profile.gem_ports[1];
+    assert_eq!(iptv.index, 21);
+    assert_eq!(iptv.tcont_id, 5);
+    assert_eq!(iptv.service_type, "ETH");
+    assert_eq!(iptv.encrypt, "on");
+    assert_eq!(iptv.mappings.len(), 2);
+    assert_eq!(iptv.mappings[0].priority, Some(4));
+    assert_eq!(iptv.mappings[0].port_type.as_deref(), Some("ETH"));
+    assert_eq!(iptv.mappings[0].port_id, Some(2));
+    assert_eq!(iptv.mappings[1].port_type, None);
+
+    assert_eq!(profile.gem_for_vlan(1063), Some(20));
+    assert_eq!(profile.gem_for_vlan(1501), Some(21));
+    assert_eq!(profile.gem_for_vlan(99), None);
+}
+
+#[test]
+fn parse_service_profile_fixture() {
+    let profile = parse_service_profile(include_str!("fixtures/ont_srvprofile.txt"))
+        .expect("expected service profile");
+
+    assert_eq!(profile.id, 35);
+    assert_eq!(profile.name, "ONT_BRIDGE");
+    assert_eq!(profile.binding_times, Some(1023));
+
+    assert_eq!(profile.ports.len(), 6);
+    let eth = &profile.ports[1];
+    assert_eq!(eth.port_type, "ETH");
+    assert!(eth.adaptive);
+    assert_eq!(eth.count, None);
+    assert_eq!(eth.max_adaptive, Some(8));
+    let catv = &profile.ports[5];
+    assert!(!catv.adaptive);
+    assert_eq!(catv.count, Some(1));
+
+    assert_eq!(profile.native_vlans.len(), 2);
+    assert_eq!(profile.native_vlans[0].port_type, OntPortType::Eth);
+    assert_eq!(profile.native_vlans[0].vlan, 20);
+    assert_eq!(profile.native_vlans[1].ont_port, 2);
+    assert_eq!(profile.native_vlans[1].priority, 4);
+}
+
+#[test]
+fn parse_dba_profiles_fixture() {
+    let profiles = parse_dba_profiles(include_str!("fixtures/dba_profile_all.txt"));
+    assert_eq!(profiles.len(), 4);
+    assert_eq!(profiles[0].dba_type, 3);
+    assert_eq!(profiles[0].assure_kbps, 8192);
+    assert_eq!(profiles[2].id, 10);
+    assert_eq!(profiles[2].max_kbps, 1_048_576);
+    assert_eq!(profiles[2].binding_times, 412);
+    assert!(profiles[3].bandwidth_compensate);
+    assert!(!profiles[1].bandwidth_compensate);
+}
+
+#[test]
+fn dba_bandwidth_arguments() {
+    assert_eq!(
+        DbaBandwidth::Fix { fix: 1024 }.arguments(),
+        "type1 fix 1024"
+    );
+    assert_eq!(
+        DbaBandwidth::AssureMax {
+            assure: 8192,
+            max: 20480
+        }
+        .arguments(),
+        "type3 assure 8192 max 20480"
+    );
+    assert_eq!(
+        DbaBandwidth::Max { max: 1_048_576 }.arguments(),
+        "type4 max 1048576"
+    );
+
+    let json = serde_json::to_string(&DbaBandwidth::Max { max: 1024 }).expect("serialize");
+    assert_eq!(json, r#"{"type":"max","max":1024}"#);
+}
+
+#[test]
+fn port_count_display() {
+    assert_eq!(PortCount::Adaptive.to_string(), "adaptive");
+    assert_eq!(PortCount::Fixed(4).to_string(), "4");
+}
+
+#[test]
+fn dba_profile_add_command_quotes_name() {
+    assert_eq!(
+        dba_profile_add_command(10, "FTTH 100M", DbaBandwidth::Max { max: 102_400 })
+            .expect("valid name"),
+        "dba-profile add profile-id 10 profile-name \"FTTH 100M\" type4 max 102400"
+    );
+    assert!(dba_profile_add_command(10, "FTTH \"100M\"", DbaBandwidth::Fix { fix: 1024 }).is_err());
+    assert!(dba_profile_add_command(10, "", DbaBandwidth::Fix { fix: 1024 }).is_err());
+}
+
+#[test]
+fn session_level_profile_prompts() {
+    assert_eq!(
+        SessionLevel::GponLineProfile.profile_prompt(10).as_deref(),
+        Some("(config-gpon-lineprofile-10)#")
+    );
+    assert_eq!(
+        SessionLevel::GponServiceProfile
+            .profile_prompt(35)
+            .as_deref(),
+        Some("(config-gpon-srvprofile-35)#")
+    );
+    assert_eq!(SessionLevel::Config.profile_prompt(10), None);
+    assert_eq!(SessionLevel::InterfaceGpon.profile_prompt(10), None);
+}
+
+#[test]
+fn session_level_profile_enter_commands() {
+    assert_eq!(
+        SessionLevel::GponLineProfile
+            .profile_enter_command(60, Some("FTTH_1063"))
+            .expect("valid name"),
+        "ont-lineprofile gpon profile-id 60 profile-name \"FTTH_1063\""
+    );
+    assert_eq!(
+        SessionLevel::GponServiceProfile
+            .profile_enter_command(35, None)
+            .expect("no name"),
+        "ont-srvprofile gpon profile-id 35"
+    );
+    assert!(SessionLevel::GponLineProfile
+        .profile_enter_command(60, Some("a\"b"))
+        .is_err());
+    assert!(SessionLevel::GponLineProfile
+        .profile_enter_command(60, Some("a\nquit"))
+        .is_err());
+    assert!(SessionLevel::Config
+        .profile_enter_command(60, None)
+        .is_err());
+}
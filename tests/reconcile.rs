@@ -0,0 +1,358 @@
+mod support;
+
+use oltcore::reconcile::{apply, observe, plan};
+use oltcore::{
+    ActionResult, DesiredOnt, DesiredServicePort, Fsp, NativeVlan, ObservedOnt, ObservedState,
+    OntPortType, PlanAction, ServicePort,
+};
+use support::{FakeOlt, FakeOnt};
+
+const PORT: Fsp = Fsp {
+    frame: 0,
+    slot: 9,
+    port: 2,
+};
+
+fn desired() -> DesiredOnt {
+    DesiredOnt {
+        sn: "HWTC-CB8FBDB4".to_string(),
+        fsp: PORT,
+        description: "JFTECH".to_string(),
+        line_profile_id: 60,
+        service_profile_id: 35,
+        native_vlans: vec![NativeVlan {
+            port_type: OntPortType::Eth,
+            ont_port: 1,
+            vlan: 20,
+            priority: 0,
+        }],
+        service_ports: vec![DesiredServicePort {
+            vlan: 1063,
+            gemport: 20,
+            user_vlan: 20,
+            inbound_traffic_table: 10,
+            outbound_traffic_table: 10,
+        }],
+    }
+}
+
+fn observed() -> ObservedOnt {
+    ObservedOnt {
+        fsp: PORT,
+        id: 0,
+        sn: "48575443CB8FBDB4".to_string(),
+        description: "JFTECH".to_string(),
+        line_profile_id: Some(60),
+        service_profile_id: Some(35),
+        native_vlans: None,
+        service_ports: vec![ServicePort {
+            index: 68,
+            vlan: 1063,
+            vlan_attr: "common".to_string(),
+            port_type: "gpon".to_string(),
+            fsp: PORT,
+            ont_id: Some(0),
+            gemport: Some(20),
+            flow_type: "vlan".to_string(),
+            flow_para: "20".to_string(),
+            inbound_traffic_table: Some(10),
+            outbound_traffic_table: Some(10),
+            state: "up".to_string(),
+        }],
+    }
+}
+
+#[test]
+fn plan_in_sync_is_empty() {
+    let state = ObservedState {
+        onts: vec![observed()],
+    };
+    let plan = plan(&[desired()], &state, true);
+    assert!(plan.is_empty(), "unexpected actions: {:?}", plan.actions);
+}
+
+#[test]
+fn plan_missing_ont_is_created_in_order() {
+    let plan = plan(&[desired()], &ObservedState::default(), false);
+    let phases: Vec<u8> = plan.actions.iter().map(PlanAction::phase).collect();
+    assert_eq!(phases, vec![3, 4, 5]);
+
+    match &plan.actions[0] {
+        PlanAction::AddOnt { sn, fsp, .. } => {
+            assert_eq!(sn, "48575443CB8FBDB4");
+            assert_eq!(*fsp, PORT);
+        }
+        other => panic!("expected AddOnt, got {other:?}"),
+    }
+    assert!(matches!(
+        plan.actions[2],
+        PlanAction::AddServicePort { ont_id: None, .. }
+    ));
+}
+
+#[test]
+fn plan_changed_description_and_profile_is_modified() {
+    let mut have = observed();
+    have.description = "OLD".to_string();
+    have.line_profile_id = Some(61);
+    let state = ObservedState { onts: vec![have] };
+
+    let plan = plan(&[desired()], &state, false);
+    assert_eq!(
+        plan.actions,
+        vec![PlanAction::ModifyOnt {
+            fsp: PORT,
+            ont_id: 0,
+            sn: "48575443CB8FBDB4".to_string(),
+            description: Some("JFTECH".to_string()),
+            line_profile_id: Some(60),
+            service_profile_id: None,
+        }]
+    );
+}
+
+#[test]
+fn plan_native_vlan_drift_is_corrected() {
+    let mut have = observed();
+    have.native_vlans = Some(vec![NativeVlan {
+        port_type: OntPortType::Eth,
+        ont_port: 1,
+        vlan: 1,
+        priority: 0,
+    }]);
+    let state = ObservedState { onts: vec![have] };
+
+    let plan = plan(&[desired()], &state, false);
+    assert_eq!(
+        plan.actions,
+        vec![PlanAction::SetNativeVlan {
+            fsp: PORT,
+            ont_id: Some(0),
+            sn: "48575443CB8FBDB4".to_string(),
+            native_vlan: desired().native_vlans[0].clone(),
+        }]
+    );
+}
+
+#[test]
+fn plan_service_port_vlan_change_deletes_before_adding() {
+    let mut want = desired();
+    want.service_ports[0].vlan = 1064;
+    let state = ObservedState {
+        onts: vec![observed()],
+    };
+
+    let plan = plan(&[want], &state, false);
+    assert_eq!(plan.actions.len(), 2);
+    assert!(matches!(
+        plan.actions[0],
+        PlanAction::DeleteServicePort { index: 68, .. }
+    ));
+    assert!(matches!(
+        plan.actions[1],
+        PlanAction::AddServicePort {
+            ont_id: Some(0),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn plan_service_port_gemport_change_is_recreated() {
+    let mut want = desired();
+    want.service_ports[0].gemport = 21;
+    let state = ObservedState {
+        onts: vec![observed()],
+    };
+
+    let plan = plan(&[want], &state, false);
+    let phases: Vec<u8> = plan.actions.iter().map(PlanAction::phase).collect();
+    assert_eq!(phases, vec![0, 5]);
+}
+
+#[test]
+fn plan_service_port_traffic_table_change_is_recreated() {
+    let mut want = desired();
+    want.service_ports[0].inbound_traffic_table = 11;
+    let state = ObservedState {
+        onts: vec![observed()],
+    };
+
+    let plan = plan(&[want], &state, false);
+    let phases: Vec<u8> = plan.actions.iter().map(PlanAction::phase).collect();
+    assert_eq!(phases, vec![0, 5]);
+}
+
+#[test]
+fn plan_moved_ont_is_recreated() {
+    let mut have = observed();
+    have.fsp = Fsp {
+        frame: 0,
+        slot: 9,
+        port: 3,
+    };
+    let state = ObservedState { onts: vec![have] };
+
+    let plan = plan(&[desired()], &state, false);
+    let phases: Vec<u8> = plan.actions.iter().map(PlanAction::phase).collect();
+    assert_eq!(phases, vec![0, 1, 3, 4, 5]);
+}
+
+#[test]
+fn plan_prune_only_when_requested() {
+    let mut stray = observed();
+    stray.id = 7;
+    stray.sn = "5A544547D4253ECE".to_string();
+    stray.service_ports.clear();
+    let state = ObservedState {
+        onts: vec![observed(), stray],
+    };
+
+    assert!(plan(&[desired()], &state, false).is_empty());
+
+    let plan = plan(&[desired()], &state, true);
+    assert_eq!(
+        plan.actions,
+        vec![PlanAction::DeleteOnt {
+            fsp: PORT,
+            ont_id: 7,
+            sn: "5A544547D4253ECE".to_string(),
+        }]
+    );
+}
+
+fn fake_ont(id: u32, sn: &str) -> FakeOnt {
+    FakeOnt {
+        fsp: PORT,
+        id,
+        sn: sn.to_string(),
+        description: "JFTECH".to_string(),
+        line_profile_id: 60,
+        service_profile_id: 35,
+        native_vlans: vec![NativeVlan {
+            port_type: OntPortType::Eth,
+            ont_port: 1,
+            vlan: 1,
+            priority: 0,
+        }],
+    }
+}
+
+fn errors(results: &[ActionResult]) -> Vec<Option<&str>> {
+    results.iter().map(|r| r.error.as_deref()).collect()
+}
+
+#[test]
+fn observe_reads_summary_profiles_service_ports_and_native_vlans() {
+    let mut olt = FakeOlt::default()
+        .with_ont(fake_ont(0, "48575443CB8FBDB4"), &[(68, 1063, 20, 20)])
+        .with_ont(fake_ont(7, "5A544547D4253ECE"), &[]);
+
+    let state = observe(&mut olt, &[desired()], &[]).expect("observe should succeed");
+    assert_eq!(state.onts.len(), 2);
+
+    let ont = &state.onts[0];
+    assert_eq!(ont.id, 0);
+    assert_eq!(ont.line_profile_id, Some(60));
+    assert_eq!(ont.service_ports.len(), 1);
+    assert_eq!(ont.native_vlans.as_ref().map(Vec::len), Some(1));
+
+    // The stray ONT is only seen in the summary
+    assert_eq!(state.onts[1].line_profile_id, None);
+    assert_eq!(state.onts[1].native_vlans, None);
+}
+
+#[test]
+fn observe_propagates_read_errors() {
+    let mut olt = FakeOlt::default()
+        .with_ont(fake_ont(0, "48575443CB8FBDB4"), &[])
+        .fail_on("display service-port");
+
+    assert!(observe(&mut olt, &[desired()], &[]).is_err());
+}
+
+#[test]
+fn apply_runs_actions_in_plan_order() {
+    let mut olt = FakeOlt::default().with_ont(fake_ont(3, "5A544547D4253ECE"), &[(5, 100, 1, 100)]);
+    let state = observe(&mut olt, &[desired()], &[PORT]).expect("observe should succeed");
+    let actions = plan(&[desired()], &state, true);
+    olt.calls.clear();
+
+    let results = apply(&mut olt, &actions);
+    assert!(results.iter().all(ActionResult::is_ok), "{results:?}");
+    assert_eq!(
+        olt.changes(),
+        vec![
+            "undo service-port 5",
+            "ont delete 0/9/2 3",
+            "ont add 0/9/2 48575443CB8FBDB4",
+            "ont port native-vlan 0/9/2 0 eth 1 vlan 20",
+            "service-port vlan 1063 0/9/2 ont 0",
+        ]
+    );
+    assert_eq!(results[2].ont_id, Some(0));
+    assert_eq!(results[4].ont_id, Some(0));
+    let vlan_checks = olt.calls.iter().filter(|c| *c == "display vlan all");
+    assert_eq!(vlan_checks.count(), 1);
+
+    // A second pass finds nothing to do
+    let state = observe(&mut olt, &[desired()], &[PORT]).expect("observe should succeed");
+    assert!(plan(&[desired()], &state, true).is_empty());
+}
+
+#[test]
+fn apply_stops_before_changes_when_vlan_check_fails() {
+    let mut olt = FakeOlt::default()
+        .with_ont(fake_ont(3, "5A544547D4253ECE"), &[(5, 100, 1, 100)])
+        .fail_on("display vlan all");
+    let state = observe(&mut olt, &[desired()], &[PORT]).expect("observe should succeed");
+    let actions = plan(&[desired()], &state, true);
+
+    let results = apply(&mut olt, &actions);
+    assert_eq!(results.len(), actions.actions.len());
+    assert!(errors(&results)
+        .iter()
+        .all(|e| e.is_some_and(|e| e.contains("display vlan all"))));
+    assert!(olt.changes().is_empty());
+}
+
+#[test]
+fn apply_skips_actions_of_ont_that_failed_to_add() {
+    let mut other = desired();
+    other.sn = "5A544547D4253ECE".to_string();
+    other.native_vlans.clear();
+
+    let mut olt = FakeOlt::default().fail_on("ont add 0/9/2 48575443CB8FBDB4");
+    let plan = plan(&[desired(), other], &ObservedState::default(), false);
+
+    let results = apply(&mut olt, &plan);
+    let errors = errors(&results);
+    assert_eq!(results.len(), 5);
+    assert!(errors[0].is_some_and(|e| e.contains("injected failure")));
+    assert_eq!(errors[1], None);
+    assert!(errors[2].is_some_and(|e| e.contains("was not added")));
+    assert!(errors[3].is_some_and(|e| e.contains("was not added")));
+    assert_eq!(errors[4], None);
+    assert_eq!(results[4].ont_id, Some(0));
+
+    assert!(olt.ont_by_sn("48575443CB8FBDB4").is_none());
+    assert_eq!(olt.service_ports.len(), 1);
+}
+
+#[test]
+fn apply_continues_after_failed_action() {
+    let mut olt = FakeOlt::default()
+        .with_ont(fake_ont(0, "48575443CB8FBDB4"), &[(68, 1063, 20, 20)])
+        .fail_on("ont port native-vlan");
+    let state = observe(&mut olt, &[desired()], &[]).expect("observe should succeed");
+    let mut want = desired();
+    want.description = "NEW".to_string();
+    let plan = plan(&[want], &state, false);
+
+    let results = apply(&mut olt, &plan);
+    let errors = errors(&results);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0], None);
+    assert!(errors[1].is_some());
+    assert_eq!(olt.onts[0].description, "NEW");
+}
@@ -0,0 +1,77 @@
+use oltcore::{parse_running_config, Fsp, NativeVlan, OntPortType};
+
+const PORT: Fsp = Fsp {
+    frame: 0,
+    slot: 5,
+    port: 0,
+};
+
+#[test]
+fn parse_running_config_fixture() {
+    let config = parse_running_config(include_str!("fixtures/current_configuration.txt"));
+
+    assert_eq!(config.sysname.as_deref(), Some("MA5800-X7"));
+    let vlans: Vec<u32> = config.vlans.iter().map(|v| v.id).collect();
+    assert_eq!(vlans, vec![20, 1063]);
+    assert_eq!(config.port_vlans.len(), 2);
+    assert_eq!(config.port_vlans[1].vlan, 1063);
+    assert_eq!(config.port_vlans[1].fsp.port, 1);
+
+    let line_profile = &config.line_profiles[0];
+    assert_eq!(line_profile.id, 60);
+    assert_eq!(line_profile.name, "FTTH_60");
+    assert_eq!(line_profile.tconts[0].dba_profile_id, 10);
+    assert_eq!(line_profile.gem_for_vlan(20), Some(20));
+
+    let service_profile = &config.service_profiles[0];
+    assert_eq!(service_profile.id, 35);
+    assert_eq!(service_profile.ports.len(), 2);
+    assert_eq!(service_profile.ports[1].port_type, "ETH");
+    assert_eq!(service_profile.ports[1].max_adaptive, Some(8));
+    assert_eq!(service_profile.native_vlans[0].vlan, 20);
+
+    assert_eq!(config.onts.len(), 2);
+    let ont = config.ont(PORT, 1).expect("expected ONT 1");
+    assert_eq!(ont.auth, "sn-auth");
+    assert_eq!(ont.sn.as_deref(), Some("48575443CB8FBDB4"));
+    assert_eq!(ont.line_profile_id, Some(60));
+    assert_eq!(ont.service_profile_id, Some(35));
+    assert_eq!(ont.description.as_deref(), Some("JFTECH"));
+    assert_eq!(ont.native_vlans[0].port_type, OntPortType::Eth);
+    assert_eq!(ont.native_vlans[0].vlan, 20);
+
+    let service_ports = config.service_ports_for(PORT, 0);
+    assert_eq!(service_ports.len(), 1);
+    assert_eq!(service_ports[0].index, 0);
+    assert_eq!(service_ports[0].vlan, 1063);
+    assert_eq!(service_ports[0].gemport, Some(20));
+    assert_eq!(service_ports[0].user_vlan(), Some(20));
+    assert_eq!(service_ports[0].inbound_traffic_table, Some(10));
+    assert_eq!(service_ports[0].outbound_traffic_table, Some(10));
+    assert_eq!(config.service_ports[1].inbound_traffic_table, None);
+}
+
+#[test]
+fn parse_running_config_ranges() {
+    let config = parse_running_config(
+        "[vlan-config]\n vlan 100 to 102 smart\n port vlan 100 to 101 0/8 0\n#\nreturn\n",
+    );
+    let vlans: Vec<u32> = config.vlans.iter().map(|v| v.id).collect();
+    assert_eq!(vlans, vec![100, 101, 102]);
+    assert_eq!(config.port_vlans.len(), 2);
+}
+
+#[test]
+fn parse_running_config_ignores_vlan_translation() {
+    let config = parse_running_config(include_str!("fixtures/current_configuration.txt"));
+
+    assert_eq!(
+        config.service_profiles[0].native_vlans,
+        vec![NativeVlan {
+            port_type: OntPortType::Eth,
+            ont_port: 1,
+            vlan: 20,
+            priority: 0,
+        }]
+    );
+}
@@ -0,0 +1,423 @@
+//! In-memory OLT used to exercise the provisioning workflows.
+#![allow(dead_code)]
+
+use oltcore::ssh::ServicePortConfig;
+use oltcore::{
+    normalize_serial_number, Error, Fsp, NativeVlan, OntInfo, OntInfoSummary, OntInfoSummaryOnt,
+    OntInfoSummaryPort, OntPortAttribute, OntPortType, Provisioner, Result, ServicePort,
+};
+
+#[derive(Debug, Clone)]
+pub struct FakeOnt {
+    pub fsp: Fsp,
+    pub id: u32,
+    /// Serial number (raw)
+    pub sn: String,
+    pub description: String,
+    pub line_profile_id: u32,
+    pub service_profile_id: u32,
+    pub native_vlans: Vec<NativeVlan>,
+}
+
+/// Keeps ONTs and service ports in memory and records every command.
+///
+/// Like the OLT, it refuses to delete an ONT that still has service ports and to
+/// add an ONT whose serial number is already registered.
+#[derive(Debug, Default)]
+pub struct FakeOlt {
+    pub onts: Vec<FakeOnt>,
+    pub service_ports: Vec<ServicePort>,
+    /// Commands in the order they were run
+    pub calls: Vec<String>,
+    /// Commands starting with one of these prefixes fail
+    pub failures: Vec<String>,
+    board: Option<(u32, u32)>,
+}
+
+impl FakeOlt {
+    pub fn with_ont(mut self, ont: FakeOnt, service_ports: &[(u32, u32, u32, u32)]) -> Self {
+        for &(index, vlan, gemport, user_vlan) in service_ports {
+            self.service_ports.push(ServicePort {
+                index,
+                vlan,
+                vlan_attr: "common".to_string(),
+                port_type: "gpon".to_string(),
+                fsp: ont.fsp,
+                ont_id: Some(ont.id),
+                gemport: Some(gemport),
+                flow_type: "vlan".to_string(),
+                flow_para: user_vlan.to_string(),
+                inbound_traffic_table: Some(10),
+                outbound_traffic_table: Some(10),
+                state: "up".to_string(),
+            });
+        }
+        self.onts.push(ont);
+        self
+    }
+
+    pub fn fail_on(mut self, prefix: &str) -> Self {
+        self.failures.push(prefix.to_string());
+        self
+    }
+
+    /// Recorded commands that change the configuration.
+    pub fn changes(&self) -> Vec<&str> {
+        self.calls
+            .iter()
+            .map(String::as_str)
+            .filter(|c| !c.starts_with("display"))
+            .collect()
+    }
+
+    pub fn ont_by_sn(&self, sn: &str) -> Option<&FakeOnt> {
+        let sn = normalize_serial_number(sn);
+        self.onts
+            .iter()
+            .find(|o| normalize_serial_number(&o.sn) == sn)
+    }
+
+    pub fn service_ports_of(&self, fsp: Fsp, ont_id: u32) -> Vec<&ServicePort> {
+        self.service_ports
+            .iter()
+            .filter(|sp| sp.fsp == fsp && sp.ont_id == Some(ont_id))
+            .collect()
+    }
+
+    fn run(&mut self, command: String) -> Result<()> {
+        let failed = self
+            .failures
+            .iter()
+            .any(|f| command.starts_with(f.as_str()));
+        if failed {
+            return Err(Error::CommandFailed(format!("injected failure: {command}")));
+        }
+        self.calls.push(command);
+        Ok(())
+    }
+
+    fn fsp(&self, port: u32) -> Result<Fsp> {
+        let (frame, slot) = self
+            .board
+            .ok_or_else(|| Error::InvalidContext("Must be in interface mode".to_string()))?;
+        Ok(Fsp { frame, slot, port })
+    }
+
+    fn ont_mut(&mut self, fsp: Fsp, ont_id: u32) -> Result<&mut FakeOnt> {
+        self.onts
+            .iter_mut()
+            .find(|o| o.fsp == fsp && o.id == ont_id)
+            .ok_or(Error::NotFound)
+    }
+
+    fn summary_port(&self, fsp: Fsp) -> OntInfoSummaryPort {
+        let onts: Vec<OntInfoSummaryOnt> = self
+            .onts
+            .iter()
+            .filter(|o| o.fsp == fsp)
+            .map(|o| OntInfoSummaryOnt {
+                id: o.id,
+                run_state: "online".to_string(),
+                sn: o.sn.clone(),
+                description: o.description.clone(),
+                ..OntInfoSummaryOnt::default()
+            })
+            .collect();
+        let count = u32::try_from(onts.len()).unwrap_or(u32::MAX);
+
+        OntInfoSummaryPort {
+            fsp,
+            total_onts: count,
+            online_onts: count,
+            onts,
+        }
+    }
+
+    fn insert_ont(
+        &mut self,
+        fsp: Fsp,
+        ont_id: Option<u32>,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32> {
+        if self.ont_by_sn(sn).is_some() {
+            return Err(Error::CommandFailed("SN already exists".to_string()));
+        }
+        let used = |id: u32| self.onts.iter().any(|o| o.fsp == fsp && o.id == id);
+        let id = match ont_id {
+            Some(id) if used(id) => {
+                return Err(Error::CommandFailed("ONT ID already exists".to_string()))
+            }
+            Some(id) => id,
+            None => (0..128).find(|id| !used(*id)).ok_or(Error::NotFound)?,
+        };
+
+        self.onts.push(FakeOnt {
+            fsp,
+            id,
+            sn: normalize_serial_number(sn),
+            description: description.to_string(),
+            line_profile_id,
+            service_profile_id,
+            native_vlans: Vec::new(),
+        });
+        Ok(id)
+    }
+}
+
+impl Provisioner for FakeOlt {
+    fn ensure_config(&mut self) -> Result<()> {
+        self.board = None;
+        Ok(())
+    }
+
+    fn ensure_interface_gpon(&mut self, frame: u32, slot: u32) -> Result<()> {
+        self.board = Some((frame, slot));
+        Ok(())
+    }
+
+    fn display_ont_info_summary(
+        &mut self,
+        frame: u32,
+        slot: u32,
+        port: u32,
+    ) -> Result<OntInfoSummary> {
+        let fsp = Fsp { frame, slot, port };
+        self.run(format!("display ont info summary {fsp}"))?;
+        Ok(OntInfoSummary {
+            ports: vec![self.summary_port(fsp)],
+        })
+    }
+
+    fn display_ont_info_summary_frame(&mut self, frame: u32) -> Result<OntInfoSummary> {
+        self.run(format!("display ont info summary {frame}"))?;
+
+        let mut ports: Vec<Fsp> = self
+            .onts
+            .iter()
+            .map(|o| o.fsp)
+            .filter(|fsp| fsp.frame == frame)
+            .collect();
+        ports.sort_by_key(|fsp| (fsp.slot, fsp.port));
+        ports.dedup();
+        Ok(OntInfoSummary {
+            ports: ports
+                .into_iter()
+                .map(|fsp| self.summary_port(fsp))
+                .collect(),
+        })
+    }
+
+    fn display_ont_info_by_sn(&mut self, serial_number: &str) -> Result<Option<OntInfo>> {
+        self.run(format!("display ont info by-sn {serial_number}"))?;
+
+        Ok(self.ont_by_sn(serial_number).map(|o| OntInfo {
+            fsp: o.fsp,
+            id: o.id,
+            run_state: "online".to_string(),
+            sn: o.sn.clone(),
+            description: o.description.clone(),
+            line_profile_id: o.line_profile_id,
+            service_profile_id: o.service_profile_id,
+            ..OntInfo::default()
+        }))
+    }
+
+    fn display_service_port(&mut self, fsp: Fsp, ont_id: u32) -> Result<Vec<ServicePort>> {
+        self.run(format!("display service-port {fsp} ont {ont_id}"))?;
+        Ok(self
+            .service_ports_of(fsp, ont_id)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    fn display_ont_port_attribute(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+    ) -> Result<Vec<OntPortAttribute>> {
+        let fsp = self.fsp(port)?;
+        self.run(format!(
+            "display ont port attribute {fsp} {ont_id} {port_type}"
+        ))?;
+
+        let ont = self.ont_mut(fsp, ont_id)?;
+        Ok(ont
+            .native_vlans
+            .iter()
+            .filter(|n| n.port_type == port_type)
+            .map(|n| OntPortAttribute {
+                ont_id,
+                port_id: n.ont_port,
+                port_type: "GE".to_string(),
+                speed: "auto".to_string(),
+                duplex: "auto".to_string(),
+                native_vlan: Some(n.vlan),
+                priority: Some(n.priority),
+                flow_control: "off".to_string(),
+                active_state: "on".to_string(),
+            })
+            .collect())
+    }
+
+    fn ont_add(
+        &mut self,
+        port: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32> {
+        let fsp = self.fsp(port)?;
+        self.run(format!("ont add {fsp} {sn}"))?;
+        self.insert_ont(
+            fsp,
+            None,
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )
+    }
+
+    fn ont_add_with_id(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        sn: &str,
+        description: &str,
+        line_profile_id: u32,
+        service_profile_id: u32,
+    ) -> Result<u32> {
+        let fsp = self.fsp(port)?;
+        self.run(format!("ont add {fsp} {ont_id} {sn}"))?;
+        self.insert_ont(
+            fsp,
+            Some(ont_id),
+            sn,
+            description,
+            line_profile_id,
+            service_profile_id,
+        )
+    }
+
+    fn ont_delete(&mut self, port: u32, ont_id: u32) -> Result<()> {
+        let fsp = self.fsp(port)?;
+        self.run(format!("ont delete {fsp} {ont_id}"))?;
+        if !self.service_ports_of(fsp, ont_id).is_empty() {
+            return Err(Error::CommandFailed(
+                "The ONT has service virtual ports".to_string(),
+            ));
+        }
+        self.ont_mut(fsp, ont_id)?;
+        self.onts.retain(|o| !(o.fsp == fsp && o.id == ont_id));
+        Ok(())
+    }
+
+    fn ont_modify(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        description: Option<&str>,
+        line_profile_id: Option<u32>,
+        service_profile_id: Option<u32>,
+    ) -> Result<()> {
+        let fsp = self.fsp(port)?;
+        self.run(format!("ont modify {fsp} {ont_id}"))?;
+
+        let ont = self.ont_mut(fsp, ont_id)?;
+        if let Some(description) = description {
+            ont.description = description.to_string();
+        }
+        if let Some(id) = line_profile_id {
+            ont.line_profile_id = id;
+        }
+        if let Some(id) = service_profile_id {
+            ont.service_profile_id = id;
+        }
+        Ok(())
+    }
+
+    fn ont_port_native_vlan(
+        &mut self,
+        port: u32,
+        ont_id: u32,
+        port_type: OntPortType,
+        ont_port: u32,
+        vlan: u32,
+        priority: u32,
+    ) -> Result<()> {
+        let fsp = self.fsp(port)?;
+        self.run(format!(
+            "ont port native-vlan {fsp} {ont_id} {port_type} {ont_port} vlan {vlan}"
+        ))?;
+
+        let ont = self.ont_mut(fsp, ont_id)?;
+        ont.native_vlans
+            .retain(|n| !(n.port_type == port_type && n.ont_port == ont_port));
+        ont.native_vlans.push(NativeVlan {
+            port_type,
+            ont_port,
+            vlan,
+            priority,
+        });
+        Ok(())
+    }
+
+    fn check_service_port_vlans(&mut self, _vlans: &[u32]) -> Result<()> {
+        self.run("display vlan all".to_string())
+    }
+
+    fn service_port_add_unchecked(&mut self, config: &ServicePortConfig) -> Result<()> {
+        if self.board.is_some() {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+        self.run(format!(
+            "service-port vlan {} {} ont {}",
+            config.vlan, config.fsp, config.ont_id
+        ))?;
+        self.ont_mut(config.fsp, config.ont_id)?;
+
+        let index = self
+            .service_ports
+            .iter()
+            .map(|sp| sp.index + 1)
+            .max()
+            .unwrap_or(0);
+        self.service_ports.push(ServicePort {
+            index,
+            vlan: u32::try_from(config.vlan).unwrap_or_default(),
+            vlan_attr: "common".to_string(),
+            port_type: "gpon".to_string(),
+            fsp: config.fsp,
+            ont_id: Some(config.ont_id),
+            gemport: Some(config.gemport),
+            flow_type: "vlan".to_string(),
+            flow_para: config.user_vlan.to_string(),
+            inbound_traffic_table: Some(config.inbound_traffic_table),
+            outbound_traffic_table: Some(config.outbound_traffic_table),
+            state: "up".to_string(),
+        });
+        Ok(())
+    }
+
+    fn service_port_undo(&mut self, service_port_id: u32) -> Result<()> {
+        if self.board.is_some() {
+            return Err(Error::InvalidContext("Must be in config mode".to_string()));
+        }
+        self.run(format!("undo service-port {service_port_id}"))?;
+        if !self
+            .service_ports
+            .iter()
+            .any(|sp| sp.index == service_port_id)
+        {
+            return Err(Error::NotFound);
+        }
+        self.service_ports.retain(|sp| sp.index != service_port_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use oltcore::{
+    parse_occupancy, parse_system_patches, parse_system_version, parse_temperature, parse_uptime,
+};
+
+#[test]
+fn parse_system_version_fixture() {
+    let version = parse_system_version(include_str!("fixtures/display_version.txt"));
+    assert_eq!(version.version, "MA5800V100R019C10");
+    assert_eq!(version.patch.as_deref(), Some("SPC200 SPH208"));
+    assert_eq!(version.product, "MA5800-X7");
+    assert_eq!(
+        version.uptime_seconds,
+        Some(125 * 86_400 + 3 * 3_600 + 27 * 60 + 10)
+    );
+}
+
+#[test]
+fn parse_sysuptime_formats() {
+    let output = include_str!("fixtures/display_sysuptime.txt");
+    assert_eq!(
+        parse_uptime(output),
+        Some(34 * 86_400 + 5 * 3_600 + 12 * 60 + 8)
+    );
+    assert_eq!(parse_uptime("Command:\n  display sysuptime\n"), None);
+}
+
+#[test]
+fn parse_system_patches_fixture() {
+    let patches = parse_system_patches(include_str!("fixtures/display_patch.txt"));
+    assert_eq!(patches.len(), 2);
+    assert_eq!(patches[0].name, "SPC200");
+    assert_eq!(patches[0].patch_type, "C");
+    assert_eq!(patches[0].state, "Running");
+    assert_eq!(patches[1].time.as_deref(), Some("2023-05-02 14:01:12"));
+}
+
+#[test]
+fn parse_board_health_values() {
+    assert_eq!(
+        parse_occupancy(include_str!("fixtures/display_cpu.txt")),
+        Some(12)
+    );
+    assert_eq!(
+        parse_occupancy(include_str!("fixtures/display_memory.txt")),
+        Some(45)
+    );
+    assert_eq!(parse_occupancy("  Memory occupancy: 45 %\n"), Some(45));
+    assert_eq!(parse_occupancy("Failure: board not supported\n"), None);
+    assert_eq!(
+        parse_temperature(include_str!("fixtures/display_temperature.txt")),
+        Some(47)
+    );
+    assert_eq!(
+        parse_temperature("  The temperature of the board: -5C\n"),
+        Some(-5)
+    );
+}
@@ -0,0 +1,95 @@
+use oltcore::{
+    find_traffic_table, free_traffic_table_index, parse_traffic_tables, PriorityPolicy,
+    TrafficTableConfig,
+};
+
+#[test]
+fn parse_traffic_tables_fixture() {
+    let tables = parse_traffic_tables(include_str!("fixtures/traffic_table_ip.txt"));
+    let indexes: Vec<u32> = tables.iter().map(|t| t.index).collect();
+    assert_eq!(indexes, vec![0, 1, 2, 6, 10, 11, 12]);
+
+    let first = &tables[0];
+    assert_eq!(first.cir_kbps, Some(1024));
+    assert_eq!(first.cbs_bytes, Some(34768));
+    assert_eq!(first.pir_kbps, Some(2048));
+    assert_eq!(first.priority, Some(6));
+    assert_eq!(first.copy_policy, None);
+    assert_eq!(first.priority_policy, "tag-pri");
+
+    assert_eq!(tables[2].cir_kbps, None);
+    assert_eq!(tables[6].priority, None);
+}
+
+fn config() -> TrafficTableConfig {
+    TrafficTableConfig {
+        name: "FTTH_100M".to_string(),
+        cir_kbps: 102_400,
+        pir_kbps: Some(204_800),
+        priority: 0,
+        priority_policy: PriorityPolicy::LocalSetting,
+    }
+}
+
+#[test]
+fn resolve_rates_to_index() {
+    let tables = parse_traffic_tables(include_str!("fixtures/traffic_table_ip.txt"));
+
+    assert_eq!(find_traffic_table(&tables, &config()), Some(10));
+    let no_pir = TrafficTableConfig {
+        pir_kbps: None,
+        ..config()
+    };
+    assert_eq!(find_traffic_table(&tables, &no_pir), None);
+    assert_eq!(free_traffic_table_index(&tables), 7);
+}
+
+#[test]
+fn resolve_requires_matching_priority_and_policy() {
+    let tables = parse_traffic_tables(include_str!("fixtures/traffic_table_ip.txt"));
+
+    let other_priority = TrafficTableConfig {
+        priority: 5,
+        ..config()
+    };
+    assert_eq!(find_traffic_table(&tables, &other_priority), None);
+
+    let other_policy = TrafficTableConfig {
+        priority_policy: PriorityPolicy::TagInPackage,
+        ..config()
+    };
+    assert_eq!(find_traffic_table(&tables, &other_policy), None);
+
+    let tag_priority = TrafficTableConfig {
+        cir_kbps: 1024,
+        pir_kbps: Some(2048),
+        priority: 6,
+        priority_policy: PriorityPolicy::TagInPackage,
+        ..config()
+    };
+    assert_eq!(find_traffic_table(&tables, &tag_priority), Some(0));
+}
+
+#[test]
+fn traffic_table_config_arguments() {
+    assert_eq!(
+        config().arguments().expect("valid name"),
+        "name \"FTTH_100M\" cir 102400 pir 204800 priority 0 priority-policy local-setting"
+    );
+
+    let config = TrafficTableConfig {
+        pir_kbps: None,
+        priority_policy: PriorityPolicy::TagInPackage,
+        ..config()
+    };
+    assert_eq!(
+        config.arguments().expect("valid name"),
+        "name \"FTTH_100M\" cir 102400 priority 0 priority-policy tag-In-Package"
+    );
+
+    let quoted = TrafficTableConfig {
+        name: "FTTH \"100M\"".to_string(),
+        ..config
+    };
+    assert!(quoted.arguments().is_err());
+}
@@ -0,0 +1,62 @@
+use oltcore::{check_service_port_vlans, parse_vlan_detail, parse_vlans, Error, Fsp};
+
+#[test]
+fn parse_vlans_fixture() {
+    let vlans = parse_vlans(include_str!("fixtures/vlan_all.txt"));
+    let ids: Vec<u32> = vlans.iter().map(|v| v.id).collect();
+    assert_eq!(ids, vec![1, 20, 1063, 2000]);
+
+    let vlan = &vlans[2];
+    assert_eq!(vlan.vlan_type, "smart");
+    assert_eq!(vlan.attribute, "common");
+    assert_eq!(vlan.standard_port_count, 2);
+    assert_eq!(vlan.service_port_count, 148);
+    assert_eq!(vlans[3].attribute, "stacking");
+}
+
+#[test]
+fn parse_vlan_detail_fixture() {
+    let detail =
+        parse_vlan_detail(include_str!("fixtures/vlan_detail.txt")).expect("expected VLAN detail");
+    assert_eq!(detail.id, 1063);
+    assert_eq!(detail.name.as_deref(), Some("VLAN_1063"));
+    assert_eq!(detail.vlan_type, "smart");
+    assert_eq!(detail.description, None);
+    assert_eq!(detail.service_port_count, 148);
+    assert!(detail.has_uplink());
+    assert_eq!(detail.member_ports.len(), 2);
+    assert_eq!(
+        detail.member_ports[1].fsp,
+        Fsp {
+            frame: 0,
+            slot: 8,
+            port: 1
+        }
+    );
+    assert_eq!(detail.member_ports[1].native_vlan, 1);
+    assert_eq!(detail.member_ports[1].state, "down");
+}
+
+#[test]
+fn parse_vlan_detail_missing() {
+    let output = "display vlan 999\n  Failure: The VLAN does not exist\n";
+    assert!(parse_vlan_detail(output).is_none());
+}
+
+#[test]
+fn check_service_port_vlans_requires_uplink() {
+    let vlans = parse_vlans(include_str!("fixtures/vlan_all.txt"));
+    assert!(vlans[2].has_uplink());
+    assert!(!vlans[3].has_uplink());
+
+    assert!(check_service_port_vlans(&vlans, &[20, 1063]).is_ok());
+    assert!(check_service_port_vlans(&vlans, &[]).is_ok());
+    assert!(matches!(
+        check_service_port_vlans(&vlans, &[1063, 30]),
+        Err(Error::VlanNotFound(30))
+    ));
+    assert!(matches!(
+        check_service_port_vlans(&vlans, &[2000]),
+        Err(Error::VlanWithoutUplink(2000))
+    ));
+}